@@ -1,4 +1,11 @@
+use dip::{AltNames, DipWidth, Direction, PinGap, Side, Style};
+use regex::Regex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use structopt::clap::AppSettings;
 use structopt::StructOpt;
+use toml::map::Map;
+use toml::Value;
 
 #[derive(Debug)]
 pub struct Args {
@@ -6,44 +13,78 @@ pub struct Args {
     pub direction: Direction,
     pub show_pin: PinGap,
     pub show_alt: AltNames,
-    pub input: std::path::PathBuf,
+    pub columns: Vec<String>,    // headings of name columns to be shown
+    pub variant: Option<String>, // variant of specifications to be shown
+    pub grid: Option<usize>,     // drawings in a row laid out side by side
+    pub gap: usize,              // spaces between drawings side by side
+    pub style: Style,
+    pub color: Color,
+    pub overbar: bool,
+    pub diagram: bool,
+    pub format: Format,
+    pub input_format: InputFormat,
+    pub inputs: Vec<PathBuf>,
+    pub command: Command,
+    pub properties: Map<String, Value>, // properties of a pin table package
+    pub pattern: Option<Regex>,         // pattern of pin names to be found
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Side {
-    TOP,
-    BOTTOM,
+pub enum Command {
+    DRAW,
+    CONVERT,
+    DIFF,
+    FIND,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
+pub enum Color {
+    AUTO,
+    ALWAYS,
+    NEVER,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub enum PinGap {
-    NONE,
-    PIN1,
-    PIN2,
+pub enum Format {
+    TEXT,
+    SVG,
+    KICAD,
+    JSON,
+    CSV,
+    TSV,
+    MARKDOWN,
+    HTML,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub enum AltNames {
-    NONE,
-    ALT1,
-    ALT2,
-    ALL,
+pub enum InputFormat {
+    AUTO,
+    TOML,
+    JSON,
+    YAML,
+    CSV,
+    TSV,
+}
+
+impl InputFormat {
+    /// Returns the format of specification file `path` by its extension.
+    pub fn from_path(path: &Path) -> InputFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => InputFormat::JSON,
+            Some("yaml") | Some("yml") => InputFormat::YAML,
+            Some("csv") => InputFormat::CSV,
+            Some("tsv") => InputFormat::TSV,
+            _ => InputFormat::TOML,
+        }
+    }
 }
 
 #[derive(StructOpt, Debug)]
-#[structopt(name = "dip")]
+#[structopt(name = "dip", setting = AppSettings::SubcommandsNegateReqs)]
 struct Opt {
-    /// DIP specification file path
-    #[structopt(name = "specifcation_file", parse(from_os_str))]
-    input: std::path::PathBuf,
+    /// DIP specification file paths
+    #[structopt(name = "specifcation_file", parse(from_os_str), required = true)]
+    inputs: Vec<PathBuf>,
     /// Top-side output
     #[structopt(short = "t", long = "top")]
     top: bool,
@@ -77,57 +118,374 @@ struct Opt {
     /// Two alternate names output
     #[structopt(long = "alt2")]
     alt2: bool,
+    /// Name columns output by comma separated headings
+    #[structopt(long = "columns")]
+    columns: Option<String>,
+    /// Variant of specification output
+    #[structopt(long = "variant")]
+    variant: Option<String>,
+    /// Drawings output side by side
+    #[structopt(long = "side-by-side")]
+    side_by_side: bool,
+    /// Drawings output in a grid of the number of columns
+    #[structopt(long = "grid")]
+    grid: Option<usize>,
+    /// Spaces between drawings side by side
+    #[structopt(long = "gap", default_value = "4")]
+    gap: usize,
+    /// Active-low signal output with overline instead of #, but not in ascii style
+    #[structopt(long = "overbar")]
+    overbar: bool,
+    /// Drawing output above markdown or beside html pin table
+    #[structopt(long = "diagram")]
+    diagram: bool,
+    /// Drawing style of text output
+    #[structopt(long = "style", default_value = "ascii", possible_values = &["ascii", "unicode"])]
+    style: String,
+    /// Colorize text output by pin function
+    #[structopt(long = "color", default_value = "auto", possible_values = &["auto", "always", "never"])]
+    color: String,
+    /// Output format
+    #[structopt(long = "format", default_value = "text", possible_values = &["text", "svg", "kicad", "json", "csv", "tsv", "markdown", "html"])]
+    format: String,
+    /// Format of specification files, guessed from file extension if auto
+    #[structopt(long = "input-format", default_value = "auto", possible_values = &["auto", "toml", "json", "yaml", "csv", "tsv"])]
+    input_format: String,
+    #[structopt(subcommand)]
+    command: Option<Subcommand>,
+}
+
+#[derive(StructOpt, Debug)]
+enum Subcommand {
+    /// Converts a pin table or specification file to TOML specification
+    #[structopt(name = "convert")]
+    Convert(ConvertOpt),
+    /// Compares pins of two specification files
+    #[structopt(name = "diff")]
+    Diff(DiffOpt),
+    /// Finds pins by names in specification files and directories
+    #[structopt(name = "find")]
+    Find(FindOpt),
+}
+
+#[derive(StructOpt, Debug)]
+struct ConvertOpt {
+    /// Format of the input file, guessed from file extension if auto
+    #[structopt(long = "from", default_value = "auto", possible_values = &["auto", "toml", "json", "yaml", "csv", "tsv"])]
+    from: String,
+    /// Name of a pin table package, defaults to the part column or the file name
+    #[structopt(long = "name")]
+    name: Option<String>,
+    /// Title of a pin table package, defaults to the title column or the name
+    #[structopt(long = "title")]
+    title: Option<String>,
+    /// Package type of a pin table, defaults to the package column or dip
+    #[structopt(long = "package", possible_values = &["dip", "sip", "zip", "plcc", "qfj", "qfp", "qfn", "pga", "header"])]
+    package: Option<String>,
+    /// Width of a pin table package in mil, defaults to the width column or 300
+    #[structopt(long = "width")]
+    width: Option<i64>,
+    /// Pin table or specification file path
+    #[structopt(name = "input_file", parse(from_os_str))]
+    input: PathBuf,
+}
+
+#[derive(StructOpt, Debug)]
+struct DiffOpt {
+    /// Specification file path of the original part
+    #[structopt(name = "original_file", parse(from_os_str))]
+    original: PathBuf,
+    /// Specification file path of the replacement part
+    #[structopt(name = "replacement_file", parse(from_os_str))]
+    replacement: PathBuf,
+}
+
+#[derive(StructOpt, Debug)]
+struct FindOpt {
+    /// Pattern as a regular expression instead of a glob
+    #[structopt(long = "regex")]
+    regex: bool,
+    /// Glob pattern of pin names, which may have * and ?
+    #[structopt(name = "pattern")]
+    pattern: String,
+    /// Specification file or directory paths
+    #[structopt(name = "path", parse(from_os_str), required = true)]
+    paths: Vec<PathBuf>,
+}
+
+/// Error in command line arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArgsError {
+    /// Mutually exclusive `options` are specified together.
+    Conflict { options: &'static [&'static str] },
+    /// `value` of an option is not one of the choices for `what`.
+    UnknownValue { what: &'static str, value: String },
+    /// `pattern` of pin names is not a valid glob or regular expression.
+    InvalidPattern { pattern: String },
+    /// More than one input file is given for `format`, which has one
+    /// drawing per document.
+    MultipleInputs { format: &'static str },
+    /// `command` has no output in `format`.
+    UnsupportedFormat {
+        command: &'static str,
+        format: String,
+    },
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgsError::Conflict { options } if options.len() == 2 => {
+                write!(f, "Both {} and {} are specified", options[0], options[1])
+            }
+            ArgsError::Conflict { options } => {
+                write!(f, "More than one of {} are specified", options.join(" "))
+            }
+            ArgsError::UnknownValue { what, value } => write!(f, "unknown {} {}", what, value),
+            ArgsError::InvalidPattern { pattern } => write!(f, "invalid pattern {}", pattern),
+            ArgsError::MultipleInputs { format } => {
+                write!(f, "--format {} takes only one input file", format)
+            }
+            ArgsError::UnsupportedFormat { command, format } => {
+                write!(f, "dip {} doesn't support --format {}", command, format)
+            }
+        }
+    }
 }
 
-const ERR_SIDE: &str = "Both -t and -b are specified";
-const ERR_DIRECTION: &str = "More than one of -n -e -s -w are specified";
-const ERR_PIN_NUMBER: &str = "Both --pin and --pin2 are specified";
-const ERR_ALT_NAMES: &str = "More than one of --alt --alt1 --alt2 are specified";
+const ERR_SIDE: ArgsError = ArgsError::Conflict {
+    options: &["-t", "-b"],
+};
+const ERR_DIRECTION: ArgsError = ArgsError::Conflict {
+    options: &["-n", "-e", "-s", "-w"],
+};
+const ERR_PIN_NUMBER: ArgsError = ArgsError::Conflict {
+    options: &["--pin", "--pin2"],
+};
+const ERR_ALT_NAMES: ArgsError = ArgsError::Conflict {
+    options: &["--alt", "--alt1", "--alt2"],
+};
+const ERR_COLUMNS: ArgsError = ArgsError::Conflict {
+    options: &["--columns", "--alt"],
+};
+const ERR_GRID: ArgsError = ArgsError::Conflict {
+    options: &["--side-by-side", "--grid"],
+};
 
-fn parse_side(opt: &Opt) -> Result<Side, String> {
+fn unknown(what: &'static str, value: &str) -> ArgsError {
+    ArgsError::UnknownValue {
+        what,
+        value: value.to_string(),
+    }
+}
+
+fn parse_side(opt: &Opt) -> Result<Side, ArgsError> {
     match (opt.top, opt.bottom) {
-        (true, true) => Err(ERR_SIDE.to_string()),
+        (true, true) => Err(ERR_SIDE),
         (false, true) => Ok(Side::BOTTOM),
         (_, false) => Ok(Side::TOP),
     }
 }
 
-fn parse_direction(opt: &Opt) -> Result<Direction, String> {
+fn parse_direction(opt: &Opt) -> Result<Direction, ArgsError> {
     match (opt.north, opt.east, opt.south, opt.west) {
         (_, false, false, false) => Ok(Direction::NORTH),
         (false, true, false, false) => Ok(Direction::EAST),
         (false, false, true, false) => Ok(Direction::SOUTH),
         (false, false, false, true) => Ok(Direction::WEST),
-        _ => Err(ERR_DIRECTION.to_string()),
+        _ => Err(ERR_DIRECTION),
     }
 }
 
-fn parse_pins(opt: &Opt) -> Result<PinGap, String> {
+fn parse_pins(opt: &Opt) -> Result<PinGap, ArgsError> {
     match (opt.pin, opt.pin2) {
         (false, false) => Ok(PinGap::NONE),
         (true, false) => Ok(PinGap::PIN1),
         (false, true) => Ok(PinGap::PIN2),
-        _ => Err(ERR_PIN_NUMBER.to_string()),
+        _ => Err(ERR_PIN_NUMBER),
     }
 }
 
-fn parse_alt_names(opt: &Opt) -> Result<AltNames, String> {
+fn parse_alt_names(opt: &Opt) -> Result<AltNames, ArgsError> {
     match (opt.alt, opt.alt1, opt.alt2) {
+        _ if opt.columns.is_some() && (opt.alt || opt.alt1 || opt.alt2) => Err(ERR_COLUMNS),
+        (false, false, false) if opt.columns.is_some() => Ok(AltNames::ALL),
         (false, false, false) => Ok(AltNames::NONE),
         (true, false, false) => Ok(AltNames::ALL),
         (false, true, false) => Ok(AltNames::ALT1),
         (false, false, true) => Ok(AltNames::ALT2),
-        _ => Err(ERR_ALT_NAMES.to_string()),
+        _ => Err(ERR_ALT_NAMES),
+    }
+}
+
+fn parse_columns(columns: &str) -> Vec<String> {
+    columns.split(',').map(|c| c.trim().to_string()).collect()
+}
+
+fn parse_grid(opt: &Opt) -> Result<Option<usize>, ArgsError> {
+    match (opt.side_by_side, opt.grid) {
+        (true, Some(_)) => Err(ERR_GRID),
+        (true, None) => Ok(Some(opt.inputs.len())),
+        (false, grid) => Ok(grid),
+    }
+}
+
+fn parse_style(opt: &Opt) -> Result<Style, ArgsError> {
+    match opt.style.as_str() {
+        "ascii" => Ok(Style::ASCII),
+        "unicode" => Ok(Style::UNICODE),
+        style => Err(unknown("drawing style", style)),
+    }
+}
+
+fn parse_color(opt: &Opt) -> Result<Color, ArgsError> {
+    match opt.color.as_str() {
+        "auto" => Ok(Color::AUTO),
+        "always" => Ok(Color::ALWAYS),
+        "never" => Ok(Color::NEVER),
+        color => Err(unknown("color mode", color)),
+    }
+}
+
+fn parse_format(opt: &Opt) -> Result<Format, ArgsError> {
+    match opt.format.as_str() {
+        "text" => Ok(Format::TEXT),
+        "svg" => Ok(Format::SVG),
+        "kicad" => Ok(Format::KICAD),
+        "json" => Ok(Format::JSON),
+        "csv" => Ok(Format::CSV),
+        "tsv" => Ok(Format::TSV),
+        "markdown" => Ok(Format::MARKDOWN),
+        "html" => Ok(Format::HTML),
+        format => Err(unknown("output format", format)),
+    }
+}
+
+fn parse_input_format(format: &str) -> Result<InputFormat, ArgsError> {
+    match format {
+        "auto" => Ok(InputFormat::AUTO),
+        "toml" => Ok(InputFormat::TOML),
+        "json" => Ok(InputFormat::JSON),
+        "yaml" => Ok(InputFormat::YAML),
+        "csv" => Ok(InputFormat::CSV),
+        "tsv" => Ok(InputFormat::TSV),
+        format => Err(unknown("input format", format)),
     }
 }
 
-pub fn parse_args() -> Result<Args, String> {
-    let opt = Opt::from_args();
-    Ok(Args {
+fn parse_width(width: i64) -> Result<DipWidth, ArgsError> {
+    DipWidth::from_mil(width).ok_or_else(|| unknown("DIP width", &width.to_string()))
+}
+
+/// Returns the regular expression of `pattern`, which is converted from
+/// a glob matching whole names unless `regex` is true.
+fn parse_pattern(pattern: &str, regex: bool) -> Result<Regex, ArgsError> {
+    let expr = if regex {
+        pattern.to_string()
+    } else {
+        let glob = regex::escape(pattern)
+            .replace("\\*", ".*")
+            .replace("\\?", ".");
+        format!("^{}$", glob)
+    };
+    Regex::new(&expr).map_err(|_| ArgsError::InvalidPattern {
+        pattern: pattern.to_string(),
+    })
+}
+
+pub fn parse_args() -> Result<Args, ArgsError> {
+    let mut opt = Opt::from_args();
+    let command = opt.command.take();
+    let mut args = Args {
         side: parse_side(&opt)?,
         direction: parse_direction(&opt)?,
         show_pin: parse_pins(&opt)?,
         show_alt: parse_alt_names(&opt)?,
-        input: opt.input,
-    })
+        columns: opt.columns.as_deref().map_or_else(Vec::new, parse_columns),
+        variant: opt.variant.take(),
+        grid: parse_grid(&opt)?,
+        gap: opt.gap,
+        style: parse_style(&opt)?,
+        color: parse_color(&opt)?,
+        overbar: opt.overbar,
+        diagram: opt.diagram,
+        format: parse_format(&opt)?,
+        input_format: parse_input_format(&opt.input_format)?,
+        inputs: opt.inputs,
+        command: Command::DRAW,
+        properties: Map::new(),
+        pattern: None,
+    };
+    if let Some(Subcommand::Find(find)) = command {
+        args.command = Command::FIND;
+        args.pattern = Some(parse_pattern(&find.pattern, find.regex)?);
+        args.inputs = find.paths;
+    } else if let Some(Subcommand::Diff(diff)) = command {
+        args.command = Command::DIFF;
+        args.inputs = vec![diff.original, diff.replacement];
+    } else if let Some(Subcommand::Convert(convert)) = command {
+        args.command = Command::CONVERT;
+        args.input_format = parse_input_format(&convert.from)?;
+        args.inputs = vec![convert.input];
+        if let Some(name) = convert.name {
+            args.properties
+                .insert("name".to_string(), Value::from(name));
+        }
+        if let Some(title) = convert.title {
+            args.properties
+                .insert("title".to_string(), Value::from(title));
+        }
+        if let Some(package) = convert.package {
+            args.properties
+                .insert("package".to_string(), Value::from(package));
+        }
+        if let Some(width) = convert.width {
+            let width = parse_width(width)?.mil() as i64;
+            args.properties
+                .insert("width".to_string(), Value::from(width));
+        }
+    }
+    if args.command == Command::DIFF && args.format != Format::TEXT {
+        return Err(ArgsError::UnsupportedFormat {
+            command: "diff",
+            format: opt.format,
+        });
+    }
+    if args.command == Command::DRAW && args.format == Format::SVG && args.inputs.len() > 1 {
+        return Err(ArgsError::MultipleInputs { format: "svg" });
+    }
+
+    Ok(args)
+}
+
+#[test]
+fn test_args_error() {
+    assert_eq!(ERR_SIDE.to_string(), "Both -t and -b are specified");
+    assert_eq!(
+        ERR_DIRECTION.to_string(),
+        "More than one of -n -e -s -w are specified"
+    );
+    assert_eq!(
+        unknown("output format", "pdf").to_string(),
+        "unknown output format pdf"
+    );
+    let glob = parse_pattern("SC?", false).unwrap();
+    assert!(glob.is_match("SCL") && !glob.is_match("ASCL"));
+    assert!(parse_pattern("SC[LK]", true).unwrap().is_match("ASCK"));
+    assert_eq!(
+        parse_pattern("(", true).unwrap_err().to_string(),
+        "invalid pattern ("
+    );
+    assert_eq!(
+        ArgsError::MultipleInputs { format: "svg" }.to_string(),
+        "--format svg takes only one input file"
+    );
+    assert_eq!(
+        ArgsError::UnsupportedFormat {
+            command: "diff",
+            format: "json".to_string()
+        }
+        .to_string(),
+        "dip diff doesn't support --format json"
+    );
 }
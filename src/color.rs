@@ -0,0 +1,77 @@
+use crate::pin::{self, PinType};
+
+const RESET: &str = "\x1b[0m";
+const POWER: &str = "\x1b[31m"; // red
+const GROUND: &str = "\x1b[90m"; // grey
+const ACTIVE_LOW: &str = "\x1b[35m"; // magenta
+const COLUMNS: [&str; 4] = [
+    "\x1b[36m", // cyan
+    "\x1b[32m", // green
+    "\x1b[33m", // yellow
+    "\x1b[34m", // blue
+];
+
+/// Returns `text`, which is a part of pin `name` at alternate name
+/// `column`, in ANSI color of the pin function. The pin function is
+/// guessed from `name` unless `pin_type` is specified.
+pub fn paint(
+    color: bool,
+    text: &str,
+    name: &str,
+    column: usize,
+    pin_type: Option<PinType>,
+) -> String {
+    if !color || text.trim().is_empty() {
+        return text.to_string();
+    }
+    let code = if pin_type.map_or(is_power(name), |t| t == PinType::POWER) {
+        POWER
+    } else if pin_type.map_or(is_ground(name), |t| t == PinType::GROUND) {
+        GROUND
+    } else if pin::is_active_low(name) {
+        ACTIVE_LOW
+    } else if column == 0 {
+        return text.to_string();
+    } else {
+        COLUMNS[(column - 1) % COLUMNS.len()]
+    };
+
+    format!("{}{}{}", code, text, RESET)
+}
+
+fn is_power(name: &str) -> bool {
+    let name = name.to_uppercase();
+    name.contains("VCC")
+        || name.contains("VDD")
+        || name == "VIN"
+        || name == "VBAT"
+        || name == "VSTBY"
+        || (name.ends_with('V') && name[..name.len() - 1].parse::<f32>().is_ok())
+}
+
+fn is_ground(name: &str) -> bool {
+    let name = name.to_uppercase();
+    name.contains("GND") || name.ends_with("VSS")
+}
+
+#[test]
+fn test_paint() {
+    assert_eq!(paint(false, "VCC", "VCC", 0, None), "VCC");
+    assert_eq!(paint(true, "VCC", "VCC", 0, None), "\x1b[31mVCC\x1b[0m");
+    assert_eq!(paint(true, "3.3V", "3.3V", 0, None), "\x1b[31m3.3V\x1b[0m");
+    assert_eq!(paint(true, "G", "AGND", 0, None), "\x1b[90mG\x1b[0m");
+    assert_eq!(paint(true, "Vss", "Vss", 0, None), "\x1b[90mVss\x1b[0m");
+    assert_eq!(
+        paint(true, "#RESET", "#RESET", 2, None),
+        "\x1b[35m#RESET\x1b[0m"
+    );
+    assert_eq!(paint(true, "PB3", "PB3", 0, None), "PB3");
+    assert_eq!(paint(true, "D11", "D11", 1, None), "\x1b[36mD11\x1b[0m");
+    assert_eq!(paint(true, "VMA", "VMA", 0, None), "VMA");
+    assert_eq!(paint(true, " ", "VCC", 0, None), " ");
+    assert_eq!(paint(true, "VCC", "VCC", 0, Some(PinType::INPUT)), "VCC");
+    assert_eq!(
+        paint(true, "E", "E", 0, Some(PinType::POWER)),
+        "\x1b[31mE\x1b[0m"
+    );
+}
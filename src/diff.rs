@@ -0,0 +1,190 @@
+use crate::dip::Dip;
+use crate::pin::PinName;
+use crate::print;
+use std::cmp::max;
+use std::collections::BTreeSet;
+
+/// A pin which differs between two packages.
+#[derive(Debug, PartialEq)]
+pub struct PinDiff {
+    pub pin: usize,     // pin number
+    pub breaking: bool, // pin is missing or its name or type differs
+}
+
+impl Dip {
+    /// Returns pins which differ from `other` by names or electrical
+    /// type. A type is compared only if both pins have it, and a
+    /// difference only in alternate names is not breaking.
+    pub fn diff(&self, other: &Dip) -> Vec<PinDiff> {
+        let pins: BTreeSet<usize> = self.pins().chain(other.pins()).map(|(n, _)| n).collect();
+        pins.into_iter()
+            .filter_map(|pin| match (self.pin(pin), other.pin(pin)) {
+                (Some(a), Some(b)) => {
+                    let types = a.pin_type() == b.pin_type()
+                        || a.pin_type().is_none()
+                        || b.pin_type().is_none();
+                    let breaking = a.name() != b.name() || !types;
+                    (breaking || a.names() != b.names()).then_some(PinDiff { pin, breaking })
+                }
+                _ => Some(PinDiff {
+                    pin,
+                    breaking: true,
+                }),
+            })
+            .collect()
+    }
+
+    /// Returns true if `other` is a drop-in replacement of this package,
+    /// which has the same package and pins of the same primary names.
+    pub fn is_compatible(&self, other: &Dip) -> bool {
+        self.package_label() == other.package_label()
+            && self.diff(other).iter().all(|d| !d.breaking)
+    }
+
+    /// Returns lines of a table of pins which differ from `other`.
+    pub fn diff_table(&self, other: &Dip) -> Vec<String> {
+        let describe = |pin: Option<&PinName>| match pin {
+            None => "-".to_string(),
+            Some(pin) => match pin.pin_type() {
+                None => pin.names().join(", "),
+                Some(t) => format!("{} ({})", pin.names().join(", "), t),
+            },
+        };
+        let mut rows = vec![[
+            "Pin".to_string(),
+            self.name.to_string(),
+            other.name.to_string(),
+            String::new(),
+        ]];
+        for d in self.diff(other) {
+            let note = if d.breaking { "" } else { "(compatible)" };
+            rows.push([
+                other.pin_label(d.pin),
+                describe(self.pin(d.pin)),
+                describe(other.pin(d.pin)),
+                note.to_string(),
+            ]);
+        }
+        let widths: Vec<usize> = (0..3)
+            .map(|c| rows.iter().map(|row| print::width(&row[c])).fold(0, max))
+            .collect();
+
+        let mut out = Vec::new();
+        if self.package_label() != other.package_label() {
+            out.push(format!(
+                "package {} and {} differ",
+                self.package_label(),
+                other.package_label()
+            ));
+        }
+        for row in &rows {
+            let line = format!(
+                "{}  {}  {}  {}",
+                print::right(widths[0], &row[0]),
+                print::left(widths[1], &row[1]),
+                print::left(widths[2], &row[2]),
+                row[3]
+            );
+            out.push(line.trim_end().to_string());
+        }
+        out.push(format!(
+            "{} pins differ, {}",
+            rows.len() - 1,
+            if self.is_compatible(other) {
+                "drop-in compatible"
+            } else {
+                "not drop-in compatible"
+            }
+        ));
+
+        out
+    }
+
+    /// Returns a package of this layout combined with `other`, where a
+    /// pin of different primary names is named `A->B` and a pin with
+    /// other differences is marked by a trailing `*`.
+    pub fn combine(&self, other: &Dip) -> Dip {
+        let diff = self.diff(other);
+        let pins = self
+            .pins()
+            .map(|(n, pin)| {
+                let changed = diff.iter().any(|d| d.pin == n);
+                let name = match other.pin(n) {
+                    _ if !changed => pin.name().to_string(),
+                    Some(b) if b.name() == pin.name() => format!("{}*", pin.name()),
+                    Some(b) => format!("{}->{}", pin.name(), b.name()),
+                    None => format!("{}->", pin.name()),
+                };
+                (n, PinName::new(vec![name], None))
+            })
+            .collect();
+        let title = format!("{} vs {}", self.name, other.name);
+        self.with_pins(title, pins)
+    }
+
+    /// Returns the package type, pin count and width.
+    fn package_label(&self) -> String {
+        format!(
+            "{}{} {} mil",
+            self.package.name().to_uppercase(),
+            self.dip,
+            self.width.mil()
+        )
+    }
+}
+
+#[test]
+fn test_diff() {
+    use std::str::FromStr;
+    let a = Dip::from_str(
+        r#"
+        name = "A"
+        dip = 4
+        width = 300
+        1 = "VCC"
+        2 = "X, Y"
+        3 = { names = "D", type = "input" }
+        4 = "GND""#,
+    )
+    .unwrap();
+    let b = Dip::from_str(
+        r##"
+        name = "B"
+        dip = 4
+        width = 300
+        1 = "VCC"
+        2 = "X, Z"
+        3 = "D"
+        4 = "#OE""##,
+    )
+    .unwrap();
+    assert_eq!(
+        a.diff(&b),
+        vec![
+            PinDiff {
+                pin: 2,
+                breaking: false
+            },
+            PinDiff {
+                pin: 4,
+                breaking: true
+            },
+        ]
+    );
+    assert!(!a.is_compatible(&b));
+    assert_eq!(
+        a.diff_table(&b),
+        vec![
+            "Pin  A     B",
+            "  2  X, Y  X, Z  (compatible)",
+            "  4  GND   #OE",
+            "2 pins differ, not drop-in compatible",
+        ]
+    );
+    let names: Vec<String> = a
+        .combine(&b)
+        .pins()
+        .map(|(_, pin)| pin.name().to_string())
+        .collect();
+    assert_eq!(names, vec!["VCC", "X*", "D", "GND->#OE"]);
+}
@@ -1,11 +1,18 @@
 extern crate unicode_segmentation;
 
-use crate::cli::{AltNames, Direction, PinGap, Side};
-use crate::pin::PinName;
+use crate::error::DipError;
+use crate::header::Numbering;
+use crate::options::{AltNames, Direction, PinGap, PrintOptions, Side, Style};
+use crate::pga::{self, Grid};
+use crate::pin::{PinName, PinType};
 use crate::print;
+use crate::quad::{Pin1, Quad};
+use crate::style::Frame;
 use core::iter::Iterator;
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::{max, min};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
@@ -13,88 +20,286 @@ use toml::map::Map;
 use toml::Value;
 use unicode_segmentation::UnicodeSegmentation;
 
+/// Maximum pin count of a package.
+const MAX_PINS: i64 = 4096;
+
+/// Keys of pin count by package name, like `qfj = 44`.
+pub(crate) const SHORTHANDS: [&str; 6] = ["sip", "zip", "plcc", "qfj", "qfp", "qfn"];
+
 #[derive(Debug, PartialEq)]
 pub struct Dip {
-    pub name: String,               // IC name
-    pub title: String,              // IC title
-    pub dip: usize,                 // pin count
-    pub width: DipWidth,            // package width
-    pins: BTreeMap<usize, PinName>, // names of pins
+    pub name: String,                          // IC name
+    pub title: String,                         // IC title
+    pub(crate) package: Package,               // package type
+    pub(crate) dip: usize,                     // pin count, defined pins of PGA
+    pub(crate) width: DipWidth,                // package width
+    pub(crate) quad: Option<Quad>,             // pins on each edge of quad package
+    pub(crate) grid: Option<Grid>,             // rows and columns of pin grid array
+    pub(crate) numbering: Option<Numbering>,   // pin numbering of pin header
+    pub columns: Vec<String>,                  // headings of name columns
+    pub(crate) pins: BTreeMap<usize, PinName>, // names of pins
+    variants: BTreeMap<String, Dip>,           // resolved variants by name
 }
 
+/// Type of package, which decides the arrangement of pins.
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub enum DipWidth {
-    MIL300,
-    MIL500,
-    MIL600,
-    MIL900,
-    MIL1300,
+pub enum Package {
+    DIP,    // dual in-line, pins in two rows
+    SIP,    // single in-line, pins in a row
+    ZIP,    // zig-zag in-line, pins in a row staggered in two
+    PLCC,   // plastic leaded chip carrier, pins on four edges
+    QFP,    // quad flat package, pins on four edges
+    QFN,    // quad flat no-lead package, pins on four edges
+    PGA,    // pin grid array, pins labeled by row and column
+    HEADER, // dual-row pin header, pins numbered by a scheme
+}
+
+impl Package {
+    /// Returns the name of the package in specification.
+    pub fn name(self) -> &'static str {
+        match self {
+            Package::DIP => "dip",
+            Package::SIP => "sip",
+            Package::ZIP => "zip",
+            Package::PLCC => "plcc",
+            Package::QFP => "qfp",
+            Package::QFN => "qfn",
+            Package::PGA => "pga",
+            Package::HEADER => "header",
+        }
+    }
+
+    /// Returns true if pins are in a row on an edge of the package.
+    pub fn is_inline(self) -> bool {
+        matches!(self, Package::SIP | Package::ZIP)
+    }
+
+    /// Returns true if pins are on four edges of the package.
+    pub fn is_quad(self) -> bool {
+        matches!(self, Package::PLCC | Package::QFP | Package::QFN)
+    }
+
+    /// Returns the package of `name` in specification, if supported.
+    pub fn from_name(name: &str) -> Option<Package> {
+        match name {
+            "dip" => Some(Package::DIP),
+            "sip" => Some(Package::SIP),
+            "zip" => Some(Package::ZIP),
+            "plcc" | "qfj" => Some(Package::PLCC),
+            "qfp" => Some(Package::QFP),
+            "qfn" => Some(Package::QFN),
+            "pga" => Some(Package::PGA),
+            "header" => Some(Package::HEADER),
+            _ => None,
+        }
+    }
+}
+
+/// Row spacing of a package, a positive multiple of 100 mil.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DipWidth {
+    mil: usize, // row spacing in mil
+}
+
+impl DipWidth {
+    pub const MIL100: DipWidth = DipWidth { mil: 100 };
+    pub const MIL300: DipWidth = DipWidth { mil: 300 };
+    pub const MIL400: DipWidth = DipWidth { mil: 400 };
+    pub const MIL500: DipWidth = DipWidth { mil: 500 };
+    pub const MIL600: DipWidth = DipWidth { mil: 600 };
+    pub const MIL700: DipWidth = DipWidth { mil: 700 };
+    pub const MIL900: DipWidth = DipWidth { mil: 900 };
+    pub const MIL1300: DipWidth = DipWidth { mil: 1300 };
+
+    /// Returns the row spacing in mil.
+    pub fn mil(self) -> usize {
+        self.mil
+    }
+
+    /// Returns the width of row spacing `mil`, if it is a positive
+    /// multiple of 100.
+    pub fn from_mil(mil: i64) -> Option<DipWidth> {
+        match mil {
+            m if m > 0 && m % 100 == 0 => Some(DipWidth { mil: m as usize }),
+            _ => None,
+        }
+    }
+
+    /// Returns the value at this width on a polyline through `points`
+    /// of row spacing in 100 mil and value, extended by its end segments.
+    fn interpolate(self, points: &[(i64, i64)]) -> usize {
+        let n = (self.mil / 100) as i64;
+        let i = points
+            .iter()
+            .position(|&(x, _)| n <= x)
+            .unwrap_or(points.len() - 1)
+            .clamp(1, points.len() - 1);
+        let ((x0, y0), (x1, y1)) = (points[i - 1], points[i]);
+        (y0 + ((n - x0) * (y1 - y0)).div_euclid(x1 - x0)) as usize
+    }
+}
+
+impl fmt::Debug for DipWidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MIL{}", self.mil)
+    }
+}
+
+impl Serialize for DipWidth {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.mil() as u64)
+    }
+}
+
+impl Serialize for Dip {
+    /// Serializes with the same schema as TOML specification. The pin
+    /// count is `dip` for DIP package, otherwise `pins`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("name", &self.name)?;
+        map.serialize_entry("title", &self.title)?;
+        if self.package == Package::DIP {
+            map.serialize_entry("dip", &self.dip)?;
+            map.serialize_entry("width", &self.width)?;
+        } else {
+            map.serialize_entry("package", self.package.name())?;
+            map.serialize_entry("pins", &self.dip)?;
+        }
+        if let Some(quad) = self.quad {
+            map.serialize_entry("sides", &[quad.vertical, quad.horizontal])?;
+            map.serialize_entry("pin1", quad.pin1.name())?;
+        }
+        if let Some(grid) = self.grid {
+            map.serialize_entry("grid", &[grid.rows, grid.columns])?;
+        }
+        if let Some(numbering) = self.numbering {
+            map.serialize_entry("width", &self.width)?;
+            map.serialize_entry("numbering", numbering.name())?;
+        }
+        if !self.columns.is_empty() {
+            map.serialize_entry("columns", &self.columns)?;
+        }
+        map.serialize_entry("pin", &PinLabels(self, None))?;
+        if !self.variants.is_empty() {
+            let variants = self.variants().map(|(name, v)| (name, Overrides(self, v)));
+            map.serialize_entry("variant", &variants.collect::<BTreeMap<_, _>>())?;
+        }
+        map.end()
+    }
+}
+
+/// Pins keyed by pin labels, to be serialized. Pins same as the base
+/// package, if any, are omitted.
+struct PinLabels<'a>(&'a Dip, Option<&'a Dip>);
+
+impl Serialize for PinLabels<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let pins = match self.1 {
+            None => self.0.pins().collect(),
+            Some(base) => base.overridden_pins(self.0),
+        };
+        serializer.collect_map(pins.into_iter().map(|(n, pin)| (self.0.pin_label(n), pin)))
+    }
+}
+
+/// Name, title and pins of a variant which differ from its base
+/// package, to be serialized.
+struct Overrides<'a>(&'a Dip, &'a Dip);
+
+impl Serialize for Overrides<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (base, variant) = (self.0, self.1);
+        let mut map = serializer.serialize_map(None)?;
+        if variant.name != base.name {
+            map.serialize_entry("name", &variant.name)?;
+        }
+        if variant.title != base.title {
+            map.serialize_entry("title", &variant.title)?;
+        }
+        map.serialize_entry("pin", &PinLabels(variant, Some(base)))?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DipWidth {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mil = i64::deserialize(deserializer)?;
+        DipWidth::from_mil(mil).ok_or_else(|| {
+            de::Error::custom(format!(
+                "DIP width {} must be a positive multiple of 100",
+                mil
+            ))
+        })
+    }
 }
 
 impl Dip {
-    pub fn print(
-        &self,
-        dir: Direction,
-        side: Side,
-        show_pin: PinGap,
-        show_alt: AltNames,
-    ) -> Vec<String> {
+    /// Returns lines of text art of the package drawn by `options`.
+    pub fn print(&self, options: &PrintOptions) -> Vec<String> {
+        let PrintOptions {
+            dir,
+            side,
+            show_pin,
+            show_alt,
+            style,
+            color,
+            overbar,
+        } = *options;
+        let frame = style.frame();
+        // a combining overline is not ASCII, so # is kept
+        let overbar = overbar && style == Style::UNICODE;
+        if self.package.is_inline() {
+            return self.print_sip(dir, side, frame, show_pin, show_alt, color, overbar);
+        }
+        if self.package == Package::PGA {
+            return self.print_pga(dir, side, frame, show_pin, show_alt, color, overbar);
+        }
+        if self.package.is_quad() {
+            return self.print_quad(dir, side, frame, show_pin, show_alt, color, overbar);
+        }
+        let (start1, end1, start2, end2) = self.pin_ranges(dir, side);
         match dir {
-            Direction::NORTH => self.print_vertical(
-                side,
-                show_pin,
-                show_alt,
-                1,
-                self.dip / 2,
-                self.dip,
-                self.dip / 2 + 1,
-            ),
-            Direction::SOUTH => self.print_vertical(
-                side,
-                show_pin,
-                show_alt,
-                self.dip / 2 + 1,
-                self.dip,
-                self.dip / 2,
-                1,
-            ),
-            Direction::EAST => self.print_horizontal(
-                side,
-                show_pin,
-                show_alt,
-                self.dip / 2,
-                1,
-                self.dip / 2 + 1,
-                self.dip,
+            Direction::NORTH | Direction::SOUTH => self.print_vertical(
+                dir, frame, show_pin, show_alt, color, overbar, start1, end1, start2, end2,
             ),
-            Direction::WEST => self.print_horizontal(
-                side,
-                show_pin,
-                show_alt,
-                self.dip,
-                self.dip / 2 + 1,
-                1,
-                self.dip / 2,
+            Direction::EAST | Direction::WEST => self.print_horizontal(
+                dir, frame, show_pin, show_alt, color, overbar, start1, end1, start2, end2,
             ),
         }
     }
 
+    /// Returns start and end pin numbers of left (top) and right (bottom)
+    /// rows of the package seen from `side` and `dir`.
+    pub(crate) fn pin_ranges(&self, dir: Direction, side: Side) -> (usize, usize, usize, usize) {
+        let numbering = self.numbering.unwrap_or(Numbering::DIP);
+        let (lstart, lend, rstart, rend) = numbering.rows(self.dip);
+        let (start1, end1, start2, end2) = match dir {
+            Direction::NORTH => (lstart, lend, rstart, rend),
+            Direction::SOUTH => (rend, rstart, lend, lstart),
+            Direction::EAST => (lend, lstart, rend, rstart),
+            Direction::WEST => (rstart, rend, lstart, lend),
+        };
+        match side {
+            Side::TOP => (start1, end1, start2, end2),
+            Side::BOTTOM => (start2, end2, start1, end1),
+        }
+    }
+
     fn print_vertical(
         &self,
-        side: Side,
+        dir: Direction,
+        frame: &Frame,
         show_pin: PinGap,
         show_alt: AltNames,
-        left_start: usize,
-        left_end: usize,
-        right_start: usize,
-        right_end: usize,
+        color: bool,
+        overbar: bool,
+        lstart: usize,
+        lend: usize,
+        rstart: usize,
+        rend: usize,
     ) -> Vec<String> {
-        let (lstart, lend, rstart, rend) = match side {
-            Side::TOP => (left_start, left_end, right_start, right_end),
-            Side::BOTTOM => (right_start, right_end, left_start, left_end),
-        };
-        let (lmax, lmaxes) = self.max_name_width(lstart, lend, show_alt);
-        let (rmax, rmaxes) = self.max_name_width(rstart, rend, show_alt);
+        let (mut lmax, mut lmaxes) = self.max_name_width(lstart, lend, show_alt, overbar);
+        let (mut rmax, mut rmaxes) = self.max_name_width(rstart, rend, show_alt, overbar);
         let (lpin_width, rpin_width) = match show_pin {
             PinGap::PIN2 => (
                 self.max_pin_width(lstart, lend) + 2,
@@ -108,9 +313,29 @@ impl Dip {
         };
 
         let mut out = Vec::new();
-        let mut line = String::new();
-        line.push_str(&print::right(lmax + lpin_width + 1, " "));
-        line.push_str(&print::chars(self.dip_width(), '_'));
+        // headings are printed only with alternate names
+        if !self.columns.is_empty() && show_alt != AltNames::NONE {
+            lmax = self.fit_headings(&mut lmaxes);
+            rmax = self.fit_headings(&mut rmaxes);
+            let heading = |c: usize| self.columns.get(c).map_or("", String::as_str);
+            let left: Vec<String> = (0..lmaxes.len())
+                .rev()
+                .map(|c| print::right(lmaxes[c], heading(c)))
+                .collect();
+            let right: Vec<String> = (0..rmaxes.len())
+                .map(|c| print::left(rmaxes[c], heading(c)))
+                .collect();
+            let mut line = print::right(lmax, &left.join(" "));
+            line.push_str(&print::spaces(
+                lpin_width + self.dip_width() + 2 + rpin_width,
+            ));
+            line.push_str(&right.join(" "));
+            out.push(line.trim_end().to_string());
+        }
+        let indent = lmax + lpin_width + 1;
+        let top = &frame.vertical_top;
+        let mut line = print::spaces(indent - top.left.graphemes(true).count());
+        line.push_str(&top.line(self.dip_width(), dir == Direction::NORTH));
         out.push(line);
 
         let bottom = self.dip / 2;
@@ -119,7 +344,7 @@ impl Dip {
         let right = self.dip_width() - 1;
         let name_chars = self.name_chars();
         let num_chars = name_chars.len();
-        let name_start = (bottom - num_chars) / 2 + 1;
+        let name_start = bottom.saturating_sub(num_chars) / 2 + 1;
         let name_end = name_start + num_chars;
 
         let mut lpin = lstart;
@@ -128,39 +353,46 @@ impl Dip {
             let mut line = String::new();
             line.push_str(&print::right(
                 lmax,
-                &self.pin(lpin).names_horizontal(&lmaxes, true),
+                &self.pins[&lpin].names_horizontal(&lmaxes, true, color, overbar),
             ));
             if show_pin != PinGap::NONE {
                 line.push_str(&print::right(lpin_width, &lpin.to_string()));
             }
 
-            let spc = if pos == bottom { '_' } else { ' ' };
-            line.push('|');
+            let spc = if pos == bottom {
+                frame.vertical_last
+            } else {
+                " "
+            };
+            line.push_str(frame.vertical_side);
             for c in 0..self.dip_width() {
                 let print_name = c == center;
-                if lpin == 1 && c == left {
-                    line.push('*');
-                } else if rpin == 1 && c == right {
-                    line.push('*');
+                if (lpin == 1 && c == left) || (rpin == 1 && c == right) {
+                    line.push_str(frame.pin1);
                 } else if print_name && pos >= name_start && pos < name_end {
                     line += &name_chars[pos - name_start];
                 } else {
-                    line.push(spc);
+                    line.push_str(spc);
                 }
             }
-            line.push('|');
+            line.push_str(frame.vertical_side);
 
             if show_pin != PinGap::NONE {
                 line.push_str(&print::left(rpin_width, &rpin.to_string()));
             }
             line.push_str(&print::left(
                 rmax,
-                &self.pin(rpin).names_horizontal(&rmaxes, false),
+                &self.pins[&rpin].names_horizontal(&rmaxes, false, color, overbar),
             ));
             out.push(line);
 
-            lpin = pin_step(lpin, lstart, lend);
-            rpin = pin_step(rpin, rstart, rend);
+            lpin = self.next_pin(lpin, lstart, lend);
+            rpin = self.next_pin(rpin, rstart, rend);
+        }
+        if let Some(bottom) = &frame.vertical_bottom {
+            let mut line = print::spaces(indent - bottom.left.graphemes(true).count());
+            line.push_str(&bottom.line(self.dip_width(), dir == Direction::SOUTH));
+            out.push(line);
         }
         if show_pin != PinGap::NONE {
             let width = lmax + lpin_width + 1 + (self.dip_width() + self.title.len()) / 2;
@@ -170,74 +402,82 @@ impl Dip {
         out
     }
 
-    fn dip_width(&self) -> usize {
-        match self.width {
-            DipWidth::MIL300 => 5,
-            DipWidth::MIL500 => 7,
-            DipWidth::MIL600 => 9,
-            DipWidth::MIL900 => 15,
-            DipWidth::MIL1300 => 23,
-        }
+    /// Returns the number of columns of the body drawn vertically.
+    pub(crate) fn dip_width(&self) -> usize {
+        // 5, 7, 9, 15 and 23 columns for 300, 500, 600, 900 and 1300 mil,
+        // rounded up to odd to center the pin-1 mark and name
+        self.width
+            .interpolate(&[(3, 5), (5, 7), (6, 9), (9, 15), (13, 23)])
+            | 1
     }
 
     fn print_horizontal(
         &self,
-        side: Side,
+        dir: Direction,
+        frame: &Frame,
         show_pin: PinGap,
         show_alt: AltNames,
-        top_start: usize,
-        top_end: usize,
-        bottom_start: usize,
-        bottom_end: usize,
+        color: bool,
+        overbar: bool,
+        tstart: usize,
+        tend: usize,
+        bstart: usize,
+        bend: usize,
     ) -> Vec<String> {
-        let (tstart, tend, bstart, bend) = match side {
-            Side::TOP => (top_start, top_end, bottom_start, bottom_end),
-            Side::BOTTOM => (bottom_start, bottom_end, top_start, top_end),
-        };
         let height = self.dip_height();
         let width = self.dip / 2;
 
         let mut out = Vec::new();
-        self.print_pins_vertical(tstart, tend, show_pin, show_alt, true, &mut out);
+        self.print_pins_vertical(
+            tstart, tend, show_pin, show_alt, color, overbar, true, &mut out,
+        );
         let center = height / 2 + 1;
         let name_chars = self.name_chars();
-        let left = (self.dip - name_chars.len()) / 2;
+        // the name starts inside the left side even if it is truncated
+        let left = max(1, self.dip.saturating_sub(name_chars.len()) / 2);
         let right = left + name_chars.len();
         for l in 1..=height {
-            let mut line = String::new();
+            if l == 1 {
+                out.push(frame.horizontal_top.line(width * 2 - 1, false));
+                continue;
+            }
+            if l == height {
+                out.push(frame.horizontal_bottom.line(width * 2 - 1, false));
+                continue;
+            }
             let print_name = l == center;
+            let mut line = String::from(match dir {
+                Direction::WEST if print_name => frame.notch_left,
+                _ => frame.horizontal_side,
+            });
             let mut tpin = tstart;
             let mut bpin = bstart;
             for pos in 1..=width {
                 let c = (pos - 1) * 2;
-                line.push_str(match pos {
-                    1 if l == 1 || l == height => "+",
-                    1 => "|",
-                    _ if l == 1 || l == height => "-",
-                    _ if print_name && c >= left && c < right => &name_chars[c - left],
-                    _ => " ",
-                });
-                line.push_str(match pos {
-                    1 if l == 1 || l == height => "-",
-                    1 if l == 2 && tpin == 1 => "*",
-                    1 if l == height - 1 && bpin == 1 => "*",
-                    _ if l == 1 || l == height => "-",
-                    _ if l == 2 && tpin == 1 => "*",
-                    _ if l == height - 1 && bpin == 1 => "*",
-                    _ if print_name && c + 1 >= left && c + 1 < right => &name_chars[c + 1 - left],
+                if pos != 1 {
+                    line.push_str(match c {
+                        _ if print_name && c >= left && c < right => &name_chars[c - left],
+                        _ => " ",
+                    });
+                }
+                line.push_str(match c + 1 {
+                    _ if l == 2 && tpin == 1 => frame.pin1,
+                    _ if l == height - 1 && bpin == 1 => frame.pin1,
+                    c if print_name && c >= left && c < right => &name_chars[c - left],
                     _ => " ",
                 });
-                tpin = pin_step(tpin, tstart, tend);
-                bpin = pin_step(bpin, bstart, bend);
+                tpin = self.next_pin(tpin, tstart, tend);
+                bpin = self.next_pin(bpin, bstart, bend);
             }
-            line.push_str(match l {
-                1 => "+",
-                _ if l == height => "+",
-                _ => "|",
+            line.push_str(match dir {
+                Direction::EAST if print_name => frame.notch_right,
+                _ => frame.horizontal_side,
             });
             out.push(line);
         }
-        self.print_pins_vertical(bstart, bend, show_pin, show_alt, false, &mut out);
+        self.print_pins_vertical(
+            bstart, bend, show_pin, show_alt, color, overbar, false, &mut out,
+        );
 
         if show_pin != PinGap::NONE {
             let width = (self.dip + 1 + self.title.len()) / 2;
@@ -247,24 +487,39 @@ impl Dip {
         out
     }
 
-    fn print_pins_vertical(
+    pub(crate) fn print_pins_vertical(
         &self,
         start: usize,
         end: usize,
         show_pin: PinGap,
         show_alt: AltNames,
+        color: bool,
+        overbar: bool,
         top: bool,
         out: &mut Vec<String>,
     ) {
-        let (name_height, names_width) = self.max_name_width(start, end, show_alt);
-        let pin_height = self.max_pin_width(start, end);
-        let width = self.dip / 2;
+        let pins = self.row_pins(start, end);
+        self.print_pin_list_vertical(&pins, show_pin, show_alt, color, overbar, top, out);
+    }
+
+    /// Same as `print_pins_vertical` for pins in `pins` order.
+    pub(crate) fn print_pin_list_vertical(
+        &self,
+        pins: &[usize],
+        show_pin: PinGap,
+        show_alt: AltNames,
+        color: bool,
+        overbar: bool,
+        top: bool,
+        out: &mut Vec<String>,
+    ) {
+        let (name_height, names_width) = self.max_names_width(pins, show_alt, overbar);
+        let pin_height = self.max_pins_width(pins);
         let mut names = vec![String::new(); name_height];
-        let mut pins = vec![String::new(); pin_height];
-        let mut pin = start;
+        let mut numbers = vec![String::new(); pin_height];
         if top {
-            for _ in 1..=width {
-                let name_chars = self.pin(pin).names_vertical(&names_width, top);
+            for &pin in pins {
+                let name_chars = self.pins[&pin].names_vertical(&names_width, top, color, overbar);
                 for l in 0..name_height {
                     let line = &mut names[l];
                     line.push(' ');
@@ -273,75 +528,227 @@ impl Dip {
                 if show_pin != PinGap::NONE {
                     let pin_chars = print::bottom(pin_height, &pin.to_string());
                     for l in 0..pin_height {
-                        let line = &mut pins[l];
+                        let line = &mut numbers[l];
                         line.push(' ');
                         line.push_str(&pin_chars[l]);
                     }
                 }
-                pin = pin_step(pin, start, end);
             }
             out.append(&mut names);
             if show_pin != PinGap::NONE {
                 out.push(String::from(" "));
-                out.append(&mut pins);
+                out.append(&mut numbers);
             }
         } else {
-            for _ in 1..=width {
+            for &pin in pins {
                 if show_pin != PinGap::NONE {
                     let pin_chars = print::top(pin_height, &pin.to_string());
                     for l in 0..pin_height {
-                        let line = &mut pins[l];
+                        let line = &mut numbers[l];
                         line.push(' ');
                         line.push_str(&pin_chars[l]);
                     }
                 }
-                let name_chars = self.pin(pin).names_vertical(&names_width, top);
+                let name_chars = self.pins[&pin].names_vertical(&names_width, top, color, overbar);
                 for l in 0..name_height {
                     let line = &mut names[l];
                     line.push(' ');
                     line.push_str(&name_chars[l]);
                 }
-                pin = pin_step(pin, start, end);
             }
             if show_pin != PinGap::NONE {
-                out.append(&mut pins);
+                out.append(&mut numbers);
                 out.push(String::from(" "));
             }
             out.append(&mut names);
         }
     }
 
-    fn dip_height(&self) -> usize {
-        match self.width {
-            DipWidth::MIL300 => 4,
-            DipWidth::MIL500 => 5,
-            DipWidth::MIL600 => 6,
-            DipWidth::MIL900 => 8,
-            DipWidth::MIL1300 => 13,
-        }
+    /// Returns the number of lines of the body drawn horizontally.
+    pub(crate) fn dip_height(&self) -> usize {
+        // 4, 5, 6, 8 and 13 lines for 300, 500, 600, 900 and 1300 mil
+        max(
+            3,
+            self.width
+                .interpolate(&[(3, 4), (5, 5), (6, 6), (9, 8), (13, 13)]),
+        )
     }
 
-    fn name_chars(&self) -> Vec<String> {
+    pub(crate) fn name_chars(&self) -> Vec<String> {
         self.name
             .graphemes(true)
             .map(String::from)
             .collect::<Vec<String>>()
     }
 
-    fn pin(&self, pin_number: usize) -> &PinName {
-        &self.pins.get(&pin_number).unwrap()
+    /// Returns the package type.
+    pub fn package(&self) -> Package {
+        self.package
     }
 
-    fn max_name_width(&self, start: usize, end: usize, show_alt: AltNames) -> (usize, Vec<usize>) {
-        let limit = match show_alt {
-            AltNames::NONE => 1,
-            AltNames::ALT1 => 2,
-            AltNames::ALT2 => 3,
-            AltNames::ALL => usize::MAX,
-        };
+    /// Returns the pin count, which is the number of defined pins for
+    /// PGA package.
+    pub fn pin_count(&self) -> usize {
+        self.dip
+    }
+
+    /// Returns the package width.
+    pub fn width(&self) -> DipWidth {
+        self.width
+    }
+
+    /// Returns pins on each edge of quad package.
+    pub fn quad(&self) -> Option<Quad> {
+        self.quad
+    }
+
+    /// Returns rows and columns of PGA package.
+    pub fn grid(&self) -> Option<Grid> {
+        self.grid
+    }
+
+    /// Returns the pin numbering of pin header.
+    pub fn numbering(&self) -> Option<Numbering> {
+        self.numbering
+    }
+
+    /// Returns names of pin `pin_number`, if defined.
+    pub fn pin(&self, pin_number: usize) -> Option<&PinName> {
+        self.pins.get(&pin_number)
+    }
+
+    /// Returns the label of pin `pin_number`, which is the number itself
+    /// except for grid positions of PGA package.
+    pub fn pin_label(&self, pin_number: usize) -> String {
+        match self.grid {
+            None => pin_number.to_string(),
+            Some(grid) => grid.label(pin_number),
+        }
+    }
+
+    /// Returns an iterator over pin numbers and names in pin number order.
+    pub fn pins(&self) -> impl Iterator<Item = (usize, &PinName)> {
+        self.pins.iter().map(|(n, pin)| (*n, pin))
+    }
+
+    /// Returns a package of the same layout with `title` and `pins`.
+    pub(crate) fn with_pins(&self, title: String, pins: BTreeMap<usize, PinName>) -> Dip {
+        Dip {
+            name: self.name.clone(),
+            title,
+            package: self.package,
+            dip: self.dip,
+            width: self.width,
+            quad: self.quad,
+            grid: self.grid,
+            numbering: self.numbering,
+            columns: Vec::new(),
+            pins,
+            variants: BTreeMap::new(),
+        }
+    }
+
+    /// Returns names of variants defined in the specification.
+    pub fn variant_names(&self) -> impl Iterator<Item = &str> {
+        self.variants.keys().map(String::as_str)
+    }
+
+    /// Returns variant `name` of this package, resolved with the base
+    /// specification.
+    pub fn variant(mut self, name: &str) -> Result<Dip, DipError> {
+        self.variants
+            .remove(name)
+            .ok_or_else(|| DipError::UnknownVariant {
+                key: "variant".to_string(),
+                variant: name.to_string(),
+            })
+    }
+
+    /// Returns an iterator over variant names and resolved variants.
+    pub(crate) fn variants(&self) -> impl Iterator<Item = (&str, &Dip)> {
+        self.variants.iter().map(|(name, v)| (name.as_str(), v))
+    }
+
+    /// Returns pins of `variant` which differ from this package.
+    pub(crate) fn overridden_pins<'a>(&self, variant: &'a Dip) -> Vec<(usize, &'a PinName)> {
+        variant
+            .pins()
+            .filter(|&(n, pin)| self.pin(n) != Some(pin))
+            .collect()
+    }
+
+    /// Returns the number of name columns to be shown in a pin table.
+    pub(crate) fn name_columns(&self, show_alt: AltNames) -> usize {
+        let columns = self.pins().map(|(_, pin)| pin.names().len()).max();
+        min(columns.unwrap_or(1), show_alt.columns())
+    }
+
+    /// Returns headings of name columns of a pin table, which are
+    /// `columns` of the specification if given.
+    pub(crate) fn column_headings(&self, show_alt: AltNames) -> Vec<String> {
+        (0..self.name_columns(show_alt))
+            .map(|c| match (self.columns.get(c), c) {
+                (Some(heading), _) => heading.to_string(),
+                (None, 0) => "Name".to_string(),
+                (None, c) => format!("Alt {}", c),
+            })
+            .collect()
+    }
+
+    /// Widens `names_width` of name columns to fit their headings, and
+    /// returns the total width.
+    fn fit_headings(&self, names_width: &mut [usize]) -> usize {
+        for (width, heading) in names_width.iter_mut().zip(&self.columns) {
+            *width = max(*width, print::width(heading));
+        }
+        names_width.iter().sum::<usize>() + names_width.len().saturating_sub(1)
+    }
+
+    /// Selects name columns of all pins by `headings` of `columns`, in
+    /// the given order. A pin with a single name keeps it.
+    pub fn select_columns(&mut self, headings: &[String]) -> Result<(), DipError> {
+        let indices = headings
+            .iter()
+            .map(|heading| {
+                self.columns
+                    .iter()
+                    .position(|column| column == heading)
+                    .ok_or_else(|| DipError::UnknownColumn {
+                        key: "columns".to_string(),
+                        column: heading.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<usize>, DipError>>()?;
+        for pin in self.pins.values_mut() {
+            if pin.names().len() > 1 {
+                *pin = pin.select(&indices);
+            }
+        }
+        self.columns = headings.to_vec();
+        Ok(())
+    }
+
+    pub(crate) fn max_name_width(
+        &self,
+        start: usize,
+        end: usize,
+        show_alt: AltNames,
+        overbar: bool,
+    ) -> (usize, Vec<usize>) {
+        self.max_names_width(&self.row_pins(start, end), show_alt, overbar)
+    }
+
+    /// Same as `max_name_width` for pins in `pins`.
+    pub(crate) fn max_names_width(
+        &self,
+        pins: &[usize],
+        show_alt: AltNames,
+        overbar: bool,
+    ) -> (usize, Vec<usize>) {
+        let limit = show_alt.columns();
         let mut names_width = Vec::new();
-        for pin in min(start, end)..=max(start, end) {
-            for (i, name) in self.pin(pin).names().iter().enumerate() {
+        for &pin in pins {
+            for (i, name) in self.pins[&pin].display_names(overbar).iter().enumerate() {
                 if i >= limit {
                     break;
                 }
@@ -355,25 +762,32 @@ impl Dip {
             }
         }
         let sum: usize = names_width.iter().sum();
-        let spaces = names_width.len() - 1;
+        let spaces = names_width.len().saturating_sub(1);
 
         (sum + spaces, names_width)
     }
 
-    fn max_pin_width(&self, start: usize, end: usize) -> usize {
-        if start < 10 && end < 10 {
-            1
-        } else {
-            2
-        }
+    /// Returns the number of digits of the largest pin number in the
+    /// row from `start` to `end`.
+    pub(crate) fn max_pin_width(&self, start: usize, end: usize) -> usize {
+        max(start, end).to_string().len()
+    }
+
+    /// Returns the number of digits of the largest pin number in `pins`.
+    pub(crate) fn max_pins_width(&self, pins: &[usize]) -> usize {
+        pins.iter()
+            .map(|pin| pin.to_string().len())
+            .max()
+            .unwrap_or(1)
     }
 }
 
-fn pin_step(pin: usize, start: usize, end: usize) -> usize {
-    if start < end {
-        pin + 1
+/// Returns pin numbers from `start` to `end`, in either order.
+pub(crate) fn pin_list(start: usize, end: usize) -> Vec<usize> {
+    if start <= end {
+        (start..=end).collect()
     } else {
-        pin - 1
+        (end..=start).rev().collect()
     }
 }
 
@@ -381,109 +795,636 @@ impl fmt::Display for Dip {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "[name={} ", self.name)?;
         write!(f, "title={} ", self.title)?;
-        write!(f, "package=DIP{} withd={:?} ", self.dip, self.width)?;
-        write!(f, "pins=[")?;
-        for pin in 1..self.dip {
-            write!(f, "{} ", self.pin(pin).name())?;
-        }
-        write!(f, "{}]]", self.pin(self.dip).name())
+        let package = self.package.name().to_uppercase();
+        write!(f, "package={}{} withd={:?} ", package, self.dip, self.width)?;
+        let names: Vec<&str> = self.pins().map(|(_, pin)| pin.name()).collect();
+        write!(f, "pins=[{}]]", names.join(" "))
     }
 }
 
 impl FromStr for Dip {
-    type Err = String;
+    type Err = DipError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let v = match s.parse::<Value>() {
-            Err(err) => return Err(err.to_string()),
-            Ok(p) => p,
-        };
-        let toml = v.as_table().unwrap();
+        Dip::parse(s).map_err(|mut errors| errors.remove(0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Dip {
+    /// Deserializes a specification with the same schema and validation
+    /// as TOML, reporting the first error.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::Table(toml) => {
+                Dip::from_table(&toml).map_err(|errors| de::Error::custom(&errors[0]))
+            }
+            _ => Err(de::Error::custom("specification must be table")),
+        }
+    }
+}
+
+impl Dip {
+    /// Parses specification `s`. Unlike `from_str`, this reports all
+    /// errors found in `s`, in the order of the specification keys.
+    pub fn parse(s: &str) -> Result<Dip, Vec<DipError>> {
+        match s.parse::<Value>() {
+            Err(err) => Err(vec![DipError::from_toml(err, s)]),
+            Ok(v) => Dip::from_table(v.as_table().unwrap()),
+        }
+    }
+
+    /// Parses JSON specification `s`, which has the same schema as TOML.
+    pub fn parse_json(s: &str) -> Result<Dip, Vec<DipError>> {
+        match serde_json::from_str::<Value>(s) {
+            Err(err) => Err(vec![DipError::from_json(err)]),
+            Ok(Value::Table(toml)) => Dip::from_table(&toml),
+            Ok(_) => Err(vec![DipError::Syntax {
+                message: "specification must be object".to_string(),
+                line: 1,
+                column: 1,
+            }]),
+        }
+    }
+
+    /// Parses YAML specification `s`, which has the same schema as TOML.
+    /// Pin numbers may be written as integer keys.
+    pub fn parse_yaml(s: &str) -> Result<Dip, Vec<DipError>> {
+        match serde_yaml::from_str::<YamlValue>(s) {
+            Err(err) => Err(vec![DipError::from_yaml(err)]),
+            Ok(YamlValue(Value::Table(toml))) => Dip::from_table(&toml),
+            Ok(_) => Err(vec![DipError::Syntax {
+                message: "specification must be mapping".to_string(),
+                line: 1,
+                column: 1,
+            }]),
+        }
+    }
+
+    pub(crate) fn from_table(toml: &Map<String, Value>) -> Result<Dip, Vec<DipError>> {
+        let mut errors = Vec::new();
 
         let name = match toml.get("name") {
-            None => return Err("no name".to_string()),
+            None => Err(missing_key("name")),
             Some(v) => match v.as_str() {
-                None => return Err("name must be string".to_string()),
-                Some(str) => str.to_string(),
+                None => Err(wrong_type("name", "string")),
+                Some(str) => Ok(str.to_string()),
             },
         };
+        let name = collect(name, &mut errors);
 
         let title = match toml.get("title") {
-            None => name.to_string(),
+            None => Ok(name.clone().unwrap_or_default()),
             Some(v) => match v.as_str() {
-                None => return Err("title must be string".to_string()),
-                Some(str) => str.to_string(),
+                None => Err(wrong_type("title", "string")),
+                Some(str) => Ok(str.to_string()),
+            },
+        };
+        let title = collect(title, &mut errors);
+
+        let shorthand = SHORTHANDS
+            .iter()
+            .copied()
+            .find(|key| toml.contains_key(*key));
+        let package = match (toml.get("package"), shorthand) {
+            (None, None) => Ok(Package::DIP),
+            (None, Some(key)) => Ok(Package::from_name(key).unwrap()),
+            (Some(v), _) => match v.as_str() {
+                None => Err(wrong_type("package", "string")),
+                Some(name) => Package::from_name(name).ok_or(DipError::UnknownPackage {
+                    key: "package".to_string(),
+                    package: name.to_string(),
+                }),
             },
         };
+        let package = collect(package, &mut errors);
 
-        let dip = match toml.get("dip") {
-            None => return Err("no dip package".to_string()),
-            Some(v) => match v.as_integer() {
-                None => return Err("dip package must be number".to_string()),
-                Some(n) if n <= 0 => return Err(format!("dip package {} must be positive", n)),
-                Some(n) if n >= 80 => {
-                    return Err(format!("dip package {} must be less than 80", n))
+        let key = match (package, toml.get("pins"), shorthand) {
+            (_, Some(_), _) => "pins",
+            (_, None, Some(key)) => key,
+            (None, _, _) | (Some(Package::DIP), _, _) => "dip",
+            _ => "pins",
+        };
+        let dip = match toml.get(key) {
+            // pin count of PGA package is the number of defined pins
+            None if package == Some(Package::PGA) => Ok(0),
+            None => Err(missing_key(key)),
+            Some(v) => match (v.as_integer(), package) {
+                (None, _) => Err(wrong_type(key, "integer")),
+                (Some(n), _) if n <= 0 => Err(invalid_package(key, n, "positive")),
+                (Some(n), _) if n > MAX_PINS => Err(invalid_package(key, n, "at most 4096")),
+                (Some(n), Some(Package::DIP)) | (Some(n), Some(Package::HEADER)) if n % 2 != 0 => {
+                    Err(invalid_package(key, n, "even"))
                 }
-                Some(n) if n % 2 != 0 => return Err(format!("dip package {} must be even", n)),
-                Some(n) => usize::try_from(n).ok().unwrap(),
+                (Some(n), _) => Ok(usize::try_from(n).ok().unwrap()),
             },
         };
+        let dip = collect(dip, &mut errors);
+
+        let quad = match (package, dip) {
+            (Some(package), Some(dip)) if package.is_quad() => {
+                parse_quad(toml, package, key, dip, &mut errors).map(Some)
+            }
+            _ => Some(None),
+        };
+
+        let grid = match package {
+            Some(Package::PGA) => parse_grid(toml, &mut errors).map(Some),
+            _ => Some(None),
+        };
 
-        let width = match toml.get("width") {
-            None => return Err("no width".to_string()),
-            Some(v) => match v.as_integer() {
-                None => return Err("width must be number in mil".to_string()),
-                Some(w) if w == 300 => DipWidth::MIL300,
-                Some(w) if w == 500 => DipWidth::MIL500,
-                Some(w) if w == 600 => DipWidth::MIL600,
-                Some(w) if w == 900 => DipWidth::MIL900,
-                Some(w) if w == 1300 => DipWidth::MIL1300,
-                Some(w) => return Err(format!("unknown DIP width {}", w)),
+        let numbering = match (package, toml.get("numbering")) {
+            (Some(Package::HEADER), None) => Ok(Some(Numbering::ZIGZAG)),
+            (Some(Package::HEADER), Some(v)) => v
+                .as_str()
+                .and_then(Numbering::from_name)
+                .map(Some)
+                .ok_or_else(|| wrong_type("numbering", "\"dip\", \"zigzag\" or \"row\"")),
+            _ => Ok(None),
+        };
+        let numbering = collect(numbering, &mut errors);
+
+        let width = match (toml.get("width"), package) {
+            (None, Some(Package::HEADER)) => Ok(DipWidth::MIL100),
+            (None, Some(package)) if package != Package::DIP => Ok(DipWidth::MIL300),
+            (None, _) => Err(missing_key("width")),
+            (Some(v), _) => match v.as_integer() {
+                None => Err(wrong_type("width", "integer in mil")),
+                Some(width) => DipWidth::from_mil(width).ok_or(DipError::InvalidWidth {
+                    key: "width".to_string(),
+                    width,
+                }),
             },
         };
+        let width = collect(width, &mut errors);
+
+        let columns = match toml.get("columns") {
+            None => Ok(Vec::new()),
+            Some(v) => v
+                .as_array()
+                .and_then(|columns| {
+                    columns
+                        .iter()
+                        .map(|c| c.as_str().map(String::from))
+                        .collect::<Option<Vec<String>>>()
+                })
+                .ok_or_else(|| wrong_type("columns", "array of strings")),
+        };
+        let columns = collect(columns, &mut errors);
+
+        let headings = columns.as_deref().unwrap_or_default();
+        let pins = match grid {
+            Some(Some(grid)) => parse_pins(toml, None, Some(grid), headings, &mut errors),
+            Some(None) => parse_pins(toml, dip, None, headings, &mut errors),
+            None => BTreeMap::new(),
+        };
+        let dip = match (grid, dip) {
+            (Some(Some(_)), Some(0)) => Some(pins.len()),
+            (Some(Some(_)), Some(n)) if n != pins.len() => {
+                errors.push(invalid_package(key, n as i64, "the number of defined pins"));
+                None
+            }
+            _ => dip,
+        };
+
+        // variants are resolved only when the base specification is valid
+        let variants = if errors.is_empty() {
+            parse_variants(toml, &mut errors)
+        } else {
+            BTreeMap::new()
+        };
 
-        match pins_to_vec_result(toml, dip) {
-            Err(err) => Err(err),
-            Ok(pins) => Ok(Dip {
+        match (
+            name, title, package, dip, width, quad, grid, numbering, columns,
+        ) {
+            (
+                Some(name),
+                Some(title),
+                Some(package),
+                Some(dip),
+                Some(width),
+                Some(quad),
+                Some(grid),
+                Some(numbering),
+                Some(columns),
+            ) if errors.is_empty() => Ok(Dip {
                 name,
                 title,
+                package,
                 dip,
                 width,
+                quad,
+                grid,
+                numbering,
+                columns,
                 pins,
+                variants,
             }),
+            _ => Err(errors),
         }
     }
 }
 
-fn pins_to_vec_result(
+/// TOML value converted from YAML, whose errors are located by the
+/// YAML deserializer.
+struct YamlValue(Value);
+
+impl<'de> Deserialize<'de> for YamlValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(YamlVisitor).map(YamlValue)
+    }
+}
+
+struct YamlVisitor;
+
+impl<'de> de::Visitor<'de> for YamlVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("string, number, boolean, sequence or mapping")
+    }
+
+    fn visit_bool<E: de::Error>(self, b: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(b))
+    }
+
+    fn visit_i64<E: de::Error>(self, i: i64) -> Result<Value, E> {
+        Ok(Value::Integer(i))
+    }
+
+    fn visit_u64<E: de::Error>(self, u: u64) -> Result<Value, E> {
+        i64::try_from(u)
+            .map(Value::Integer)
+            .map_err(|_| E::custom(format!("number {} is out of range", u)))
+    }
+
+    fn visit_f64<E: de::Error>(self, f: f64) -> Result<Value, E> {
+        Ok(Value::Float(f))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Value, E> {
+        Ok(Value::String(s.to_string()))
+    }
+
+    // an unquoted name starting with # is a comment, which leaves null
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Err(E::custom(
+            "null value is not allowed, quote a name starting with # like \"#RESET\"",
+        ))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut array = Vec::new();
+        while let Some(YamlValue(v)) = seq.next_element()? {
+            array.push(v);
+        }
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut table = Map::new();
+        while let Some(YamlValue(key)) = map.next_key()? {
+            let key = match key {
+                Value::String(s) => s,
+                Value::Integer(i) => i.to_string(),
+                _ => return Err(de::Error::custom("mapping key must be string or number")),
+            };
+            let YamlValue(v) = map.next_value()?;
+            table.insert(key, v);
+        }
+        Ok(Value::Table(table))
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, _data: A) -> Result<Value, A::Error> {
+        Err(de::Error::custom("tag is not allowed"))
+    }
+}
+
+/// Returns the value of `result`, or pushes its error to `errors`.
+fn collect<T>(result: Result<T, DipError>, errors: &mut Vec<DipError>) -> Option<T> {
+    match result {
+        Ok(v) => Some(v),
+        Err(err) => {
+            errors.push(err);
+            None
+        }
+    }
+}
+
+fn missing_key(key: &str) -> DipError {
+    DipError::MissingKey {
+        key: key.to_string(),
+    }
+}
+
+fn wrong_type(key: &str, expected: &'static str) -> DipError {
+    DipError::WrongType {
+        key: key.to_string(),
+        expected,
+    }
+}
+
+fn invalid_package(key: &str, dip: i64, reason: &'static str) -> DipError {
+    DipError::InvalidPackage {
+        key: key.to_string(),
+        dip,
+        reason,
+    }
+}
+
+/// Parses `sides` and `pin1` of quad `package` of `dip` pins, which
+/// is given by `key`. Pins are divided equally into four edges unless
+/// `sides` gives pins on each left/right and top/bottom edge.
+fn parse_quad(
     toml: &Map<String, Value>,
+    package: Package,
+    key: &str,
     dip: usize,
-) -> Result<BTreeMap<usize, PinName>, String> {
+    errors: &mut Vec<DipError>,
+) -> Option<Quad> {
+    let pin1 = match toml.get("pin1") {
+        None if package == Package::PLCC => Ok(Pin1::TOPCENTER),
+        None => Ok(Pin1::LEFTTOP),
+        Some(v) => v
+            .as_str()
+            .and_then(Pin1::from_name)
+            .ok_or_else(|| wrong_type("pin1", "\"left-top\" or \"top-center\"")),
+    };
+    let pin1 = collect(pin1, errors);
+
+    let sides = match toml.get("sides").map(|v| v.as_array().map(Vec::as_slice)) {
+        None if !dip.is_multiple_of(4) => Err(invalid_package(key, dip as i64, "multiple of 4")),
+        None => Ok((dip / 4, dip / 4)),
+        Some(Some([Value::Integer(v), Value::Integer(h)])) if *v > 0 && *h > 0 => {
+            let (v, h) = (*v as usize, *h as usize);
+            if (v + h) * 2 == dip {
+                Ok((v, h))
+            } else {
+                Err(invalid_package(key, dip as i64, "twice the sum of sides"))
+            }
+        }
+        Some(_) => Err(wrong_type("sides", "array of 2 positive integers")),
+    };
+    let sides = collect(sides, errors);
+
+    match (pin1, sides) {
+        (Some(pin1), Some((vertical, horizontal))) => Some(Quad {
+            vertical,
+            horizontal,
+            pin1,
+        }),
+        _ => None,
+    }
+}
+
+/// Parses `variant` tables of `toml`, each of which overrides `name`,
+/// `title` and pins of the base specification.
+fn parse_variants(toml: &Map<String, Value>, errors: &mut Vec<DipError>) -> BTreeMap<String, Dip> {
+    let mut variants = BTreeMap::new();
+    let tables = match toml.get("variant") {
+        None => return variants,
+        Some(Value::Table(tables)) => tables,
+        Some(_) => {
+            errors.push(wrong_type("variant", "table"));
+            return variants;
+        }
+    };
+    for (name, v) in tables {
+        let key = format!("variant.{}", name);
+        let merged = match v.as_table() {
+            None => Err(wrong_type(&key, "table")),
+            Some(table) => override_table(toml, &key, table),
+        };
+        match collect(merged, errors).map(|merged| Dip::from_table(&merged)) {
+            Some(Ok(dip)) => {
+                variants.insert(name.to_string(), dip);
+            }
+            Some(Err(mut errs)) => errors.append(&mut errs),
+            None => {}
+        }
+    }
+
+    variants
+}
+
+/// Returns specification `base` overridden by `table` of variant `key`.
+fn override_table(
+    base: &Map<String, Value>,
+    key: &str,
+    table: &Map<String, Value>,
+) -> Result<Map<String, Value>, DipError> {
+    let mut merged = base.clone();
+    merged.remove("variant");
+    let mut pins = match merged.remove("pin") {
+        Some(Value::Table(pins)) => pins,
+        _ => Map::new(),
+    };
+    for (k, v) in table {
+        match k.as_str() {
+            "name" | "title" => {
+                merged.insert(k.to_string(), v.clone());
+            }
+            "pin" => match v.as_table() {
+                None => return Err(wrong_type(&format!("{}.pin", key), "table")),
+                Some(table) => {
+                    for (pin, v) in table {
+                        merged.remove(pin);
+                        pins.insert(pin.to_string(), v.clone());
+                    }
+                }
+            },
+            pin if pin.parse::<usize>().is_ok() || pga::is_label(pin) => {
+                pins.remove(pin);
+                merged.insert(pin.to_string(), v.clone());
+            }
+            _ => {
+                return Err(DipError::InvalidPin {
+                    key: format!("{}.{}", key, k),
+                })
+            }
+        }
+    }
+    if !pins.is_empty() {
+        merged.insert("pin".to_string(), Value::Table(pins));
+    }
+
+    Ok(merged)
+}
+
+/// Parses `grid` of PGA package.
+fn parse_grid(toml: &Map<String, Value>, errors: &mut Vec<DipError>) -> Option<Grid> {
+    let grid = match toml.get("grid").map(|v| v.as_array().map(Vec::as_slice)) {
+        None => Err(missing_key("grid")),
+        Some(Some([Value::Integer(rows), Value::Integer(columns)]))
+            if *rows > 0 && *columns > 0 =>
+        {
+            let pins = rows.saturating_mul(*columns);
+            if *rows > pga::MAX_ROWS as i64 {
+                Err(invalid_package("grid", pins, "within 420 rows"))
+            } else if pins > MAX_PINS {
+                Err(invalid_package("grid", pins, "at most 4096"))
+            } else {
+                Ok(Grid {
+                    rows: *rows as usize,
+                    columns: *columns as usize,
+                })
+            }
+        }
+        Some(_) => Err(wrong_type("grid", "array of 2 positive integers")),
+    };
+    collect(grid, errors)
+}
+
+/// Parses pin definitions in `toml`. Pin numbers are checked against
+/// `dip` only if it is valid. Pins of `grid` are labeled by row and
+/// column instead of numbers, and may be missing. A pin table may name
+/// a pin by headings of `columns`.
+fn parse_pins(
+    toml: &Map<String, Value>,
+    dip: Option<usize>,
+    grid: Option<Grid>,
+    columns: &[String],
+    errors: &mut Vec<DipError>,
+) -> BTreeMap<usize, PinName> {
+    let number = |key: &str| match grid {
+        None => key.parse::<usize>().ok(),
+        Some(grid) => grid.number(key),
+    };
     let mut pins = BTreeMap::new();
+    let mut defined = BTreeSet::new();
     for pin in toml.keys() {
-        if let Ok(n) = pin.parse::<usize>() {
-            if n == 0 {
-                return Err("invalid pin number 0".to_string());
+        if grid.is_some() && pga::is_label(pin) && number(pin).is_none() {
+            errors.push(DipError::InvalidPin { key: pin.clone() });
+        }
+        if let Some(n) = number(pin) {
+            defined.insert(n);
+            if let Some(name) = collect(pin_to_result(pin, n, &toml[pin], dip, columns), errors) {
+                pins.insert(n, name);
             }
-            if n > dip {
-                return Err(format!(
-                    "pin number {} must not be greater than dip {}",
-                    n, dip
-                ));
+        }
+    }
+    if let Some(v) = toml.get("pin") {
+        match v.as_table() {
+            None => errors.push(wrong_type("pin", "table")),
+            Some(table) => {
+                for (pin, v) in table {
+                    let key = format!("pin.{}", pin);
+                    let n = match number(pin) {
+                        None => {
+                            errors.push(DipError::InvalidPin { key });
+                            continue;
+                        }
+                        Some(n) => n,
+                    };
+                    if !defined.insert(n) {
+                        errors.push(DipError::DuplicatePin { key, pin: n });
+                        continue;
+                    }
+                    if let Some(name) = collect(pin_to_result(&key, n, v, dip, columns), errors) {
+                        pins.insert(n, name);
+                    }
+                }
             }
-            match toml.get(pin).unwrap().as_str() {
-                None => return Err(format!("name for pin {} must be string", n)),
-                Some(name) => pins.insert(n, PinName::from_str(name).unwrap()),
-            };
         }
     }
 
-    for p in 1..=dip {
-        if !pins.contains_key(&p) {
-            return Err(format!("missing pin {} definition", p));
+    // each range of missing pins is reported once
+    if let Some(dip) = dip {
+        let mut next = 1;
+        for &n in defined.range(1..=dip).chain(&[dip + 1]) {
+            if n > next {
+                errors.push(DipError::MissingPin {
+                    pin: next,
+                    last: n - 1,
+                });
+            }
+            next = n + 1;
+        }
+    }
+
+    pins
+}
+
+/// Returns array of names at `key`.
+fn names_to_vec(key: &str, names: &[Value]) -> Result<Vec<String>, DipError> {
+    let mut list = Vec::new();
+    for name in names {
+        match name.as_str() {
+            None => return Err(wrong_type(key, "array of string")),
+            Some(name) => list.push(name.trim().to_string()),
+        }
+    }
+    if list.is_empty() {
+        return Err(missing_key(key));
+    }
+
+    Ok(list)
+}
+
+/// Parses pin `n` definition at `key`, which is either comma separated
+/// names, an array of names or a table of `names` and optional `type`.
+pub(crate) fn pin_to_result(
+    key: &str,
+    n: usize,
+    v: &Value,
+    dip: Option<usize>,
+    columns: &[String],
+) -> Result<PinName, DipError> {
+    if let Some(dip) = dip {
+        if n == 0 || n > dip {
+            return Err(DipError::PinOutOfRange {
+                key: key.to_string(),
+                pin: n,
+                dip,
+            });
+        }
+    }
+    let (table, names_key) = match v {
+        Value::String(names) => return PinName::from_str(names),
+        Value::Array(names) => return Ok(PinName::new(names_to_vec(key, names)?, None)),
+        Value::Table(table) => (table, format!("{}.names", key)),
+        _ => return Err(wrong_type(key, "string, array or table")),
+    };
+    let mut names = match table.get("names") {
+        None => Vec::new(),
+        Some(Value::String(names)) => names.split(',').map(str::trim).map(String::from).collect(),
+        Some(Value::Array(names)) => names_to_vec(&names_key, names)?,
+        Some(_) => return Err(wrong_type(&names_key, "string or array")),
+    };
+    // a name can also be given by the heading of its column
+    for (c, heading) in columns.iter().enumerate() {
+        let name_key = format!("{}.{}", key, heading);
+        let name = match table.get(heading) {
+            None => continue,
+            Some(v) => v.as_str().ok_or_else(|| wrong_type(&name_key, "string"))?,
+        };
+        if names.len() <= c {
+            names.resize(c + 1, String::new());
+        }
+        if !names[c].is_empty() {
+            return Err(wrong_type(&name_key, "a name of an empty column"));
         }
+        names[c] = name.trim().to_string();
+    }
+    if names.is_empty() {
+        return Err(missing_key(&names_key));
     }
+    let type_key = format!("{}.type", key);
+    let pin_type = match table.get("type") {
+        None => None,
+        Some(v) => match v.as_str() {
+            None => return Err(wrong_type(&type_key, "string")),
+            Some(t) => match PinType::from_str(t) {
+                Err(_) => {
+                    return Err(DipError::UnknownPinType {
+                        key: type_key,
+                        pin_type: t.to_string(),
+                    })
+                }
+                Ok(t) => Some(t),
+            },
+        },
+    };
 
-    Ok(pins)
+    Ok(PinName::new(names, pin_type))
 }
 
 #[test]
@@ -504,10 +1445,12 @@ fn test_dip_decode() {
     assert_eq!("ATtiny412-SS", dip.title);
     assert_eq!(4, dip.dip);
     assert_eq!(DipWidth::MIL300, dip.width);
-    assert_eq!("VDD", dip.pin(1).name());
-    assert_eq!("PA6", dip.pin(2).name());
-    assert_eq!("PA7", dip.pin(3).name());
-    assert_eq!("PA1", dip.pin(4).name());
+    assert_eq!("VDD", dip.pin(1).unwrap().name());
+    assert_eq!("PA6", dip.pin(2).unwrap().name());
+    assert_eq!("PA7", dip.pin(3).unwrap().name());
+    assert_eq!("PA1", dip.pin(4).unwrap().name());
+    assert_eq!(None, dip.pin(0));
+    assert_eq!(None, dip.pin(99));
 
     assert_eq!(
         "ATtiny412",
@@ -527,51 +1470,253 @@ fn test_dip_decode() {
 }
 
 #[test]
-fn test_decode_error() {
-    assert_eq!(Dip::from_str("# empty").err(), Some("no name".to_string()));
+fn test_three_digit_pins() {
+    let pins: Vec<String> = (1..=100).map(|n| format!("{} = \"P{}\"", n, n)).collect();
+    let spec = format!("name = \"X\"\ndip = 100\nwidth = 300\n{}", pins.join("\n"));
+    let dip = Dip::from_str(&spec).unwrap();
+    assert_eq!(dip.max_pin_width(1, 50), 2);
+    assert_eq!(dip.max_pin_width(100, 51), 3);
+    let lines = dip.print(&PrintOptions {
+        show_pin: PinGap::PIN1,
+        ..PrintOptions::default()
+    });
+    assert_eq!(lines[1], " P1  1|*    |100 P100");
+    assert_eq!(lines[50], "P50 50|_____|51  P51 ");
+    let lines = dip.print(&PrintOptions {
+        dir: Direction::EAST,
+        show_pin: PinGap::PIN1,
+        ..PrintOptions::default()
+    });
+    assert!(lines[4].starts_with(" 5 4 4"));
+    assert!(lines[10].ends_with(" 9 9 1"));
+    assert_eq!(lines[12].trim_end(), print::spaces(99) + "0");
+}
+
+#[test]
+fn test_long_name() {
+    let dip = Dip::from_str(
+        "name = \"ATMEGA328P\"\ndip = 4\nwidth = 300\n1 = \"A\"\n2 = \"B\"\n3 = \"C\"\n4 = \"D\"",
+    )
+    .unwrap();
+    let print = |dir| {
+        dip.print(&PrintOptions {
+            dir,
+            ..PrintOptions::default()
+        })
+    };
     assert_eq!(
-        Dip::from_str("name = 7400").err(),
-        Some("name must be string".to_string())
+        print(Direction::NORTH),
+        vec!["  _____", "A|* A  |D", "B|__T__|C"]
     );
     assert_eq!(
-        Dip::from_str("name = \"SN7400\"").err(),
-        Some("no dip package".to_string())
+        print(Direction::EAST),
+        vec![" B A", "+---+", "|  *|", "|ATM|", "+---+", " C D"]
     );
+}
+
+#[test]
+fn test_overbar() {
+    let dip = Dip::from_str("name = \"X\"\ndip = 2\nwidth = 300\n1 = \"#A\"\n2 = \"B\"").unwrap();
+    let print = |style| {
+        dip.print(&PrintOptions {
+            style,
+            overbar: true,
+            ..PrintOptions::default()
+        })
+    };
+    assert_eq!(print(Style::ASCII)[1], "#A|*_X__|B");
+    assert_eq!(print(Style::UNICODE)[1], "A\u{305}│● X  │B");
+}
+
+#[test]
+fn test_columns() {
+    let mut dip = Dip::from_str(
+        r#"
+        name = "X"
+        dip = 4
+        width = 300
+        columns = ["Port", "Func", "Timer"]
+        1 = { names = "PA0", Timer = "T0" }
+        2 = "GND"
+        3 = "PA1, D1"
+        4 = "PA2, D2, SCK""#,
+    )
+    .unwrap();
+    assert_eq!(dip.column_headings(AltNames::ALT1), vec!["Port", "Func"]);
+    assert_eq!(dip.pin(1).unwrap().names(), vec!["PA0", "", "T0"]);
+    assert_eq!(dip.toml()[5], "1 = { names = \"PA0\", Timer = \"T0\" }");
+    assert_eq!(
+        Dip::from_str("name = \"X\"\ndip = 2\nwidth = 300\ncolumns = [\"P\", \"F\"]\n1 = { names = \"A, B\", F = \"C\" }\n2 = \"D\"").err(),
+        Some(wrong_type("1.F", "a name of an empty column"))
+    );
+    let print = |dip: &Dip| {
+        dip.print(&PrintOptions {
+            show_alt: AltNames::ALL,
+            ..PrintOptions::default()
+        })
+    };
+    assert_eq!(print(&dip)[0], "Timer Func Port       Port Func Timer");
+    assert_eq!(dip.print(&PrintOptions::default())[0], "    _____");
+    dip.select_columns(&["Timer".to_string(), "Port".to_string()])
+        .unwrap();
+    assert_eq!(
+        print(&dip),
+        vec![
+            "Port Timer       Timer Port",
+            "           _____",
+            " PA0    T0|* X  |SCK   PA2 ",
+            "       GND|_____|      PA1 ",
+        ]
+    );
+    assert_eq!(
+        dip.select_columns(&["Func".to_string()]),
+        Err(DipError::UnknownColumn {
+            key: "columns".to_string(),
+            column: "Func".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_variant() {
+    let spec = r#"
+        name = "X"
+        dip = 4
+        width = 300
+        1 = "A"
+        2 = "B"
+        3 = "C"
+        [pin.4]
+        names = "D"
+        [variant.y]
+        name = "Y"
+        2 = "BB"
+        pin.4 = "DD""#;
+    let dip = Dip::from_str(spec).unwrap();
+    assert_eq!(dip.variant_names().collect::<Vec<_>>(), vec!["y"]);
+    assert_eq!(dip.title, "X");
+    let y = Dip::from_str(spec).unwrap().variant("y").unwrap();
+    assert_eq!((y.name.as_str(), y.title.as_str()), ("Y", "Y"));
+    let names: Vec<&str> = y.pins().map(|(_, pin)| pin.name()).collect();
+    assert_eq!(names, vec!["A", "BB", "C", "DD"]);
+    assert_eq!(dip.overridden_pins(&y).len(), 2);
+    assert_eq!(
+        dip.variant("z"),
+        Err(DipError::UnknownVariant {
+            key: "variant".to_string(),
+            variant: "z".to_string(),
+        })
+    );
+    assert_eq!(
+        Dip::from_str(&format!("{}\n5 = \"E\"", spec)).err(),
+        Some(DipError::PinOutOfRange {
+            key: "5".to_string(),
+            pin: 5,
+            dip: 4,
+        })
+    );
+    assert_eq!(
+        Dip::from_str(&format!("{}\nwidth = 600", spec)).err(),
+        Some(DipError::InvalidPin {
+            key: "variant.y.width".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_dip_width() {
+    let size = |mil| {
+        let dip = Dip::from_str(&format!(
+            "name = \"X\"\ndip = 2\nwidth = {}\n1 = \"A\"\n2 = \"B\"",
+            mil
+        ));
+        let dip = dip.unwrap();
+        (dip.dip_width(), dip.dip_height())
+    };
+    assert_eq!(size(300), (5, 4));
+    assert_eq!(size(500), (7, 5));
+    assert_eq!(size(600), (9, 6));
+    assert_eq!(size(900), (15, 8));
+    assert_eq!(size(1300), (23, 13));
+    assert_eq!(size(100), (3, 3));
+    assert_eq!(size(200), (5, 3));
+    assert_eq!(size(400), (7, 4));
+    assert_eq!(size(700), (11, 6));
+    assert_eq!(size(1700), (31, 18));
+
+    let dip = Dip::from_str(
+        "name = \"X\"\ndip = 4\nwidth = 400\n1 = \"A\"\n2 = \"B\"\n3 = \"C\"\n4 = \"D\"",
+    )
+    .unwrap();
+    let print = |style| {
+        dip.print(&PrintOptions {
+            style,
+            ..PrintOptions::default()
+        })
+    };
+    assert_eq!(
+        print(Style::ASCII),
+        vec!["  _______", "A|*  X   |D", "B|_______|C"]
+    );
+    assert_eq!(
+        print(Style::UNICODE),
+        vec![" ┌──╮ ╭──┐", "A│●  X   │D", "B│       │C", " └───────┘"]
+    );
+}
+
+#[test]
+fn test_decode_error() {
+    let missing = |key: &str| Some(missing_key(key));
+    let wrong = |key: &str, expected| Some(wrong_type(key, expected));
+    let package = |dip, reason| Some(invalid_package("dip", dip, reason));
+    let width = |width| {
+        Some(DipError::InvalidWidth {
+            key: "width".to_string(),
+            width,
+        })
+    };
+    assert_eq!(Dip::from_str("# empty").err(), missing("name"));
+    assert_eq!(Dip::from_str("name = 7400").err(), wrong("name", "string"));
+    assert_eq!(Dip::from_str("name = \"SN7400\"").err(), missing("dip"));
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ntitle = 7400").err(),
-        Some("title must be string".to_string())
+        wrong("title", "string")
     );
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ndip = \"14\"").err(),
-        Some("dip package must be number".to_string())
+        wrong("dip", "integer")
     );
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ndip = -2").err(),
-        Some("dip package -2 must be positive".to_string())
+        package(-2, "positive")
     );
     assert_eq!(
-        Dip::from_str("name = \"SN7400\"\ndip = 80").err(),
-        Some("dip package 80 must be less than 80".to_string())
+        Dip::from_str("name = \"SN7400\"\ndip = 100\nwidth = 600").err(),
+        Some(DipError::MissingPin { pin: 1, last: 100 })
+    );
+    assert_eq!(
+        Dip::from_str("name = \"SN7400\"\ndip = 200000000000").err(),
+        package(200000000000, "at most 4096")
     );
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ndip = 7").err(),
-        Some("dip package 7 must be even".to_string())
+        package(7, "even")
     );
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ndip = 8").err(),
-        Some("no width".to_string())
+        missing("width")
     );
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ndip = 8\nwidth = \"300\"").err(),
-        Some("width must be number in mil".to_string())
+        wrong("width", "integer in mil")
     );
     assert_eq!(
-        Dip::from_str("name = \"SN7400\"\ndip = 8\nwidth = 200").err(),
-        Some("unknown DIP width 200".to_string())
+        Dip::from_str("name = \"SN7400\"\ndip = 8\nwidth = 0").err(),
+        width(0)
     );
     assert_eq!(
         Dip::from_str("name = \"SN7400\"\ndip = 8\nwidth = 350").err(),
-        Some("unknown DIP width 350".to_string())
+        width(350)
     );
     assert_eq!(
         Dip::from_str(
@@ -583,7 +1728,11 @@ fn test_decode_error() {
          "#
         )
         .err(),
-        Some("invalid pin number 0".to_string())
+        Some(DipError::PinOutOfRange {
+            key: "0".to_string(),
+            pin: 0,
+            dip: 8
+        })
     );
     assert_eq!(
         Dip::from_str(
@@ -595,7 +1744,11 @@ fn test_decode_error() {
          "#
         )
         .err(),
-        Some("pin number 9 must not be greater than dip 8".to_string())
+        Some(DipError::PinOutOfRange {
+            key: "9".to_string(),
+            pin: 9,
+            dip: 8
+        })
     );
     assert_eq!(
         Dip::from_str(
@@ -607,7 +1760,7 @@ fn test_decode_error() {
          "#
         )
         .err(),
-        Some("name for pin 1 must be string".to_string())
+        wrong("1", "string, array or table")
     );
     assert_eq!(
         Dip::from_str(
@@ -620,7 +1773,11 @@ fn test_decode_error() {
          "#
         )
         .err(),
-        Some("TOML parse error at line 6, column 13\n  |\n6 |             1 = \"pin2\"\n  |             ^\nduplicate key `1` in document root\n".to_string())
+        Some(DipError::Syntax {
+            message: "duplicate key `1` in document root".to_string(),
+            line: 6,
+            column: 13
+        })
     );
     assert_eq!(
         Dip::from_str(
@@ -633,6 +1790,135 @@ fn test_decode_error() {
          "#
         )
         .err(),
-        Some("missing pin 3 definition".to_string())
+        Some(DipError::MissingPin { pin: 3, last: 8 })
+    );
+    assert_eq!(
+        Dip::parse("name = \"SN7400\"\ndip = 2\nwidth = 350\n1 = 1").err(),
+        Some(vec![
+            width(350).unwrap(),
+            wrong("1", "string, array or table").unwrap(),
+            DipError::MissingPin { pin: 2, last: 2 }
+        ])
+    );
+}
+
+#[test]
+fn test_pin_table() {
+    let dip = Dip::from_str(
+        r#"
+        name = "7405"
+        dip = 4
+        width = 300
+        1 = "1A"
+        2 = { names = "1Y, Q", type = "open-collector" }
+        [pin.3]
+        names = ["GND"]
+        type = "ground"
+        [pin.4]
+        names = ["VCC", "V+"]"#,
+    )
+    .unwrap();
+    assert_eq!(None, dip.pin(1).unwrap().pin_type());
+    assert_eq!(vec!["1Y", "Q"], dip.pin(2).unwrap().names());
+    assert_eq!(Some(PinType::OPENCOLLECTOR), dip.pin(2).unwrap().pin_type());
+    assert_eq!(Some(PinType::GROUND), dip.pin(3).unwrap().pin_type());
+    assert_eq!(vec!["VCC", "V+"], dip.pin(4).unwrap().names());
+    assert_eq!(None, dip.pin(4).unwrap().pin_type());
+
+    let header = "name = \"7405\"\ndip = 2\nwidth = 300\n1 = \"A\"\n";
+    assert_eq!(
+        Dip::from_str(&format!("{}[pin.2]\ntype = \"input\"", header)).err(),
+        Some(missing_key("pin.2.names"))
+    );
+    assert_eq!(
+        Dip::from_str(&format!("{}[pin.2]\nnames = [1]", header)).err(),
+        Some(wrong_type("pin.2.names", "array of string"))
+    );
+    assert_eq!(
+        Dip::from_str(&format!(
+            "{}[pin.2]\nnames = \"Y\"\ntype = \"analog\"",
+            header
+        ))
+        .err(),
+        Some(DipError::UnknownPinType {
+            key: "pin.2.type".to_string(),
+            pin_type: "analog".to_string()
+        })
+    );
+    assert_eq!(
+        Dip::from_str(&format!("{}[pin.1]\nnames = \"Y\"", header)).err(),
+        Some(DipError::DuplicatePin {
+            key: "pin.1".to_string(),
+            pin: 1
+        })
+    );
+    assert_eq!(
+        Dip::from_str(&format!("{}[pin.X]\nnames = \"Y\"", header)).err(),
+        Some(DipError::InvalidPin {
+            key: "pin.X".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_json() {
+    let dip = Dip::from_str(
+        r#"
+        name = "7405"
+        dip = 2
+        width = 300
+        1 = "A, #OE"
+        2 = { names = "Y", type = "open-collector" }"#,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&dip).unwrap();
+    assert_eq!(
+        json,
+        r##"{"name":"7405","title":"7405","dip":2,"width":300,"pin":{"1":{"names":["A","#OE"]},"2":{"names":["Y"],"type":"open-collector"}}}"##
+    );
+    assert_eq!(Dip::parse_json(&json), Ok(dip));
+    assert_eq!(
+        serde_json::from_str::<Dip>(r#"{"name":"7405","dip":2,"width":350}"#)
+            .unwrap_err()
+            .to_string(),
+        "DIP width 350 must be a positive multiple of 100"
+    );
+}
+
+#[test]
+fn test_yaml() {
+    let yaml = Dip::parse_yaml(
+        r#"
+        name: "7405"
+        dip: 4
+        width: 300
+        1: A, B
+        2: [Y]
+        pin:
+          3: { names: GND, type: ground }
+          4:
+            names: [VCC]
+            type: power"#,
+    );
+    let toml = Dip::parse(
+        r#"
+        name = "7405"
+        dip = 4
+        width = 300
+        1 = "A, B"
+        2 = ["Y"]
+        pin.3 = { names = "GND", type = "ground" }
+        pin.4 = { names = ["VCC"], type = "power" }"#,
+    );
+    assert!(yaml.is_ok());
+    assert_eq!(yaml, toml);
+    assert_eq!(
+        Dip::parse_yaml("name: x\ndip: 2\nwidth: 350\n1: A\n2: #RESET\n").err(),
+        Some(vec![DipError::Syntax {
+            message: "2: null value is not allowed, quote a name starting with # like \"#RESET\""
+                .to_string(),
+            line: 5,
+            column: 3
+        }])
     );
 }
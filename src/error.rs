@@ -0,0 +1,339 @@
+use crate::dip::SHORTHANDS;
+use std::error;
+use std::fmt;
+use std::ops::Range;
+use toml_edit::{ImDocument, TableLike};
+
+/// Error in a DIP specification. `key` is the dotted path to the
+/// offending value, such as `width` or `pin.3.type`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DipError {
+    /// Syntax error of the specification at `line` and `column`, both
+    /// are 1-origin.
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    /// Required `key` is missing.
+    MissingKey { key: String },
+    /// Value of `key` is not `expected` type.
+    WrongType { key: String, expected: &'static str },
+    /// `package` is not a supported package type.
+    UnknownPackage { key: String, package: String },
+    /// Pin count `dip` is invalid by `reason`.
+    InvalidPackage {
+        key: String,
+        dip: i64,
+        reason: &'static str,
+    },
+    /// Package `width` is not supported.
+    InvalidWidth { key: String, width: i64 },
+    /// `key` is not a pin number.
+    InvalidPin { key: String },
+    /// Pin number `pin` is out of `1..=dip`.
+    PinOutOfRange { key: String, pin: usize, dip: usize },
+    /// Pin numbers from `pin` to `last` are not defined.
+    MissingPin { pin: usize, last: usize },
+    /// Pin number `pin` is defined more than once.
+    DuplicatePin { key: String, pin: usize },
+    /// `pin_type` is not a known electrical type.
+    UnknownPinType { key: String, pin_type: String },
+    /// `column` is not a heading of name columns.
+    UnknownColumn { key: String, column: String },
+    /// `variant` is not defined in the specification.
+    UnknownVariant { key: String, variant: String },
+}
+
+impl DipError {
+    /// Returns the dotted path to the offending value, if any.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            DipError::Syntax { .. } | DipError::MissingPin { .. } => None,
+            DipError::MissingKey { key }
+            | DipError::WrongType { key, .. }
+            | DipError::UnknownPackage { key, .. }
+            | DipError::InvalidPackage { key, .. }
+            | DipError::InvalidWidth { key, .. }
+            | DipError::InvalidPin { key }
+            | DipError::PinOutOfRange { key, .. }
+            | DipError::DuplicatePin { key, .. }
+            | DipError::UnknownPinType { key, .. }
+            | DipError::UnknownColumn { key, .. }
+            | DipError::UnknownVariant { key, .. } => Some(key),
+        }
+    }
+
+    /// Returns the 1-origin line and column of the error, if known.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            DipError::Syntax { line, column, .. } => Some((*line, *column)),
+            _ => None,
+        }
+    }
+
+    /// Converts TOML parse error of `source` text.
+    pub(crate) fn from_toml(err: toml::de::Error, source: &str) -> DipError {
+        let offset = err.span().map_or(0, |span| span.start);
+        let (line, column) = line_column(source, offset);
+        DipError::Syntax {
+            message: err.message().to_string(),
+            line,
+            column,
+        }
+    }
+
+    /// Converts JSON parse error.
+    pub(crate) fn from_json(err: serde_json::Error) -> DipError {
+        DipError::Syntax {
+            message: strip_position(err.to_string()),
+            line: err.line().max(1),
+            column: err.column().max(1),
+        }
+    }
+
+    /// Converts YAML parse error.
+    pub(crate) fn from_yaml(err: serde_yaml::Error) -> DipError {
+        let (line, column) = err.location().map_or((1, 1), |at| (at.line(), at.column()));
+        DipError::Syntax {
+            message: strip_position(err.to_string()),
+            line,
+            column,
+        }
+    }
+
+    /// Returns the byte range of the offending part of `source`, if it
+    /// can be located. Missing pins point at the pin count.
+    pub fn span(&self, source: &str) -> Option<Range<usize>> {
+        self.locate(source, ImDocument::parse(source).ok().as_ref())
+    }
+
+    /// Returns `span` of this error, where `doc` is `source` parsed as
+    /// TOML if it is.
+    fn locate(&self, source: &str, doc: Option<&ImDocument<&str>>) -> Option<Range<usize>> {
+        match self {
+            DipError::Syntax { line, column, .. } => {
+                let start = offset(source, *line, *column);
+                Some(start..start + 1)
+            }
+            DipError::MissingPin { .. } => ["dip", "pins"]
+                .iter()
+                .chain(&SHORTHANDS)
+                .find_map(|key| find(doc, key, false)),
+            DipError::InvalidPin { key }
+            | DipError::PinOutOfRange { key, .. }
+            | DipError::DuplicatePin { key, .. } => find(doc, key, true),
+            _ => find(doc, self.key()?, false),
+        }
+    }
+
+    /// Returns this error as a diagnostic of `file`, followed by the
+    /// offending line of `source` with carets under the span.
+    pub fn report(&self, file: &str, source: &str) -> String {
+        DipError::report_all(std::slice::from_ref(self), file, source)
+    }
+
+    /// Returns diagnostics of all `errors` in `source` like `report`,
+    /// which parses `source` only once.
+    pub fn report_all(errors: &[DipError], file: &str, source: &str) -> String {
+        let doc = ImDocument::parse(source).ok();
+        errors
+            .iter()
+            .map(|err| err.report_in(file, source, doc.as_ref()))
+            .collect()
+    }
+
+    fn report_in(&self, file: &str, source: &str, doc: Option<&ImDocument<&str>>) -> String {
+        let message = match self {
+            DipError::Syntax { message, .. } => message.to_string(),
+            _ => self.to_string(),
+        };
+        let span = match self.locate(source, doc) {
+            None => return format!("{}: {}\n", file, message),
+            Some(span) => span,
+        };
+        let (line, column) = line_column(source, span.start);
+        let text = source.lines().nth(line - 1).unwrap_or("");
+        let width = (text.chars().count() + 1).saturating_sub(column);
+        let carets = source[span.start..span.end.min(source.len())]
+            .chars()
+            .count()
+            .clamp(1, width.max(1));
+        let gutter = " ".repeat(line.to_string().len());
+        format!(
+            "{}:{}:{}: {}\n{} |\n{} | {}\n{} | {}{}\n",
+            file,
+            line,
+            column,
+            message,
+            gutter,
+            line,
+            text,
+            gutter,
+            " ".repeat(column - 1),
+            "^".repeat(carets)
+        )
+    }
+}
+
+impl fmt::Display for DipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DipError::Syntax {
+                message,
+                line,
+                column,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            DipError::MissingKey { key } => write!(f, "no {}", key),
+            DipError::WrongType { key, expected } => write!(f, "{} must be {}", key, expected),
+            DipError::UnknownPackage { package, .. } => write!(f, "unknown package {}", package),
+            DipError::InvalidPackage { key, dip, reason } if key == "dip" => {
+                write!(f, "dip package {} must be {}", dip, reason)
+            }
+            DipError::InvalidPackage { key, dip, reason } if key == "grid" => {
+                write!(f, "pin grid of {} pins must be {}", dip, reason)
+            }
+            DipError::InvalidPackage { dip, reason, .. } => {
+                write!(f, "pin count {} must be {}", dip, reason)
+            }
+            DipError::InvalidWidth { width, .. } => {
+                write!(f, "DIP width {} must be a positive multiple of 100", width)
+            }
+            DipError::InvalidPin { key } => write!(f, "invalid pin number {}", key),
+            DipError::PinOutOfRange { pin, dip, .. } => {
+                write!(f, "pin number {} must be between 1 and dip {}", pin, dip)
+            }
+            DipError::MissingPin { pin, last } if pin == last => {
+                write!(f, "missing pin {} definition", pin)
+            }
+            DipError::MissingPin { pin, last } => {
+                write!(f, "missing pins {}-{} definitions", pin, last)
+            }
+            DipError::DuplicatePin { key, .. } => {
+                let label = key.rsplit('.').next().unwrap();
+                write!(f, "duplicate pin {} definition", label)
+            }
+            DipError::UnknownPinType { key, pin_type } => {
+                write!(f, "unknown pin type {} for {}", pin_type, key)
+            }
+            DipError::UnknownColumn { column, .. } => write!(f, "unknown column {}", column),
+            DipError::UnknownVariant { variant, .. } => write!(f, "unknown variant {}", variant),
+        }
+    }
+}
+
+impl error::Error for DipError {}
+
+/// Strips " at line L column C" suffix of `message`, which is reported
+/// separately.
+fn strip_position(message: String) -> String {
+    match message.rfind(" at line ") {
+        Some(at) => message[..at].to_string(),
+        None => message,
+    }
+}
+
+/// Returns the span of dotted `key` in TOML `doc`, or of its closest
+/// existing parent. Points at the last key itself if `at_key` is true,
+/// otherwise at its value.
+fn find(doc: Option<&ImDocument<&str>>, key: &str, at_key: bool) -> Option<Range<usize>> {
+    let mut table: &dyn TableLike = doc?.as_table();
+    let mut span = None;
+    for part in key.split('.') {
+        let (k, item) = match table.get_key_value(part) {
+            None => break,
+            Some(kv) => kv,
+        };
+        span = if at_key {
+            k.span().or_else(|| item.span())
+        } else {
+            item.span().or_else(|| k.span())
+        };
+        match item.as_table_like() {
+            None => break,
+            Some(t) => table = t,
+        }
+    }
+
+    span
+}
+
+/// Returns the byte offset of 1-origin `line` and `column` in `source`.
+fn offset(source: &str, line: usize, column: usize) -> usize {
+    let mut offset = 0;
+    for (n, text) in source.split_inclusive('\n').enumerate() {
+        if n + 1 == line {
+            return offset
+                + text
+                    .char_indices()
+                    .nth(column - 1)
+                    .map_or(text.len(), |(i, _)| i);
+        }
+        offset += text.len();
+    }
+
+    offset
+}
+
+/// Returns the 1-origin line and column of byte `offset` in `source`.
+pub(crate) fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+
+    (line, column)
+}
+
+#[test]
+fn test_line_column() {
+    assert_eq!(line_column("abc\ndef", 0), (1, 1));
+    assert_eq!(line_column("abc\ndef", 2), (1, 3));
+    assert_eq!(line_column("abc\ndef", 4), (2, 1));
+    assert_eq!(line_column("abc\ndef", 7), (2, 4));
+    assert_eq!(
+        DipError::PinOutOfRange {
+            key: "9".to_string(),
+            pin: 9,
+            dip: 8
+        }
+        .to_string(),
+        "pin number 9 must be between 1 and dip 8"
+    );
+}
+
+#[test]
+fn test_report() {
+    let source = "name = \"7400\"\ndip = 4\nwidth = 350\n";
+    let width = DipError::InvalidWidth {
+        key: "width".to_string(),
+        width: 350,
+    };
+    assert_eq!(width.span(source), Some(30..33));
+    assert_eq!(
+        width.report("7400.toml", source),
+        "7400.toml:3:9: DIP width 350 must be a positive multiple of 100\n  |\n3 | width = 350\n  |         ^^^\n"
+    );
+    assert_eq!(
+        DipError::MissingPin { pin: 1, last: 1 }.report("7400.toml", source),
+        "7400.toml:2:7: missing pin 1 definition\n  |\n2 | dip = 4\n  |       ^\n"
+    );
+    let name = DipError::MissingKey {
+        key: "name".to_string(),
+    };
+    assert_eq!(name.report("x.toml", "dip = 4"), "x.toml: no name\n");
+    // a span past the end of a line, like at \n of \r\n
+    let eol = DipError::Syntax {
+        message: "expected newline".to_string(),
+        line: 1,
+        column: 5,
+    };
+    assert_eq!(
+        eol.report("x.toml", "abc\r\n"),
+        "x.toml:1:5: expected newline\n  |\n1 | abc\n  |     ^\n"
+    );
+    let missing = DipError::MissingPin { pin: 1, last: 1 };
+    assert_eq!(
+        DipError::report_all(&[width.clone(), missing.clone()], "7400.toml", source),
+        width.report("7400.toml", source) + &missing.report("7400.toml", source)
+    );
+}
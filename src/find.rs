@@ -0,0 +1,109 @@
+use crate::dip::Dip;
+use crate::options::AltNames;
+use crate::print;
+use std::cmp::max;
+
+/// A pin name which matches a pattern.
+#[derive(Debug, PartialEq)]
+pub struct PinMatch {
+    pub pin: usize,    // pin number
+    pub column: usize, // index of the name in the pin names
+}
+
+impl Dip {
+    /// Returns pin names, including alternate names, for which
+    /// `matches` returns true.
+    pub fn find(&self, matches: &dyn Fn(&str) -> bool) -> Vec<PinMatch> {
+        self.pins()
+            .flat_map(|(pin, name)| {
+                name.names()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, name)| matches(name))
+                    .map(move |(column, _)| PinMatch { pin, column })
+            })
+            .collect()
+    }
+}
+
+/// Returns lines of a table of part names, pin labels, names and
+/// column headings of pin names of `dips` for which `matches` returns
+/// true. Names which a variant overrides are listed as `part/variant`.
+pub fn table(dips: &[Dip], matches: &dyn Fn(&str) -> bool) -> Vec<String> {
+    let mut rows = vec![[
+        "Part".to_string(),
+        "Pin".to_string(),
+        "Name".to_string(),
+        "Column".to_string(),
+    ]];
+    for dip in dips {
+        let mut parts = vec![(dip.name.to_string(), dip)];
+        for (name, variant) in dip.variants() {
+            parts.push((format!("{}/{}", dip.name, name), variant));
+        }
+        for (i, (part, found)) in parts.into_iter().enumerate() {
+            let headings = found.column_headings(AltNames::ALL);
+            for m in found.find(matches) {
+                let name = found.pins[&m.pin].names()[m.column];
+                let base = dip
+                    .pin(m.pin)
+                    .and_then(|pin| pin.names().get(m.column).copied());
+                if i != 0 && base == Some(name) {
+                    continue;
+                }
+                rows.push([
+                    part.to_string(),
+                    found.pin_label(m.pin),
+                    name.to_string(),
+                    headings[m.column].to_string(),
+                ]);
+            }
+        }
+    }
+    let widths: Vec<usize> = (0..3)
+        .map(|c| rows.iter().map(|row| print::width(&row[c])).fold(0, max))
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            format!(
+                "{}  {}  {}  {}",
+                print::left(widths[0], &row[0]),
+                print::right(widths[1], &row[1]),
+                print::left(widths[2], &row[2]),
+                row[3]
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_find() {
+    use std::str::FromStr;
+    let dip = Dip::from_str(
+        r#"
+        name = "MCU"
+        dip = 4
+        width = 300
+        columns = ["Port", "Function"]
+        1 = "VCC"
+        2 = "PC5, SCL"
+        3 = "PC4, SDA"
+        4 = "GND"
+
+        [variant.B]
+        3 = "PC7, SDA""#,
+    )
+    .unwrap();
+    let scl = |name: &str| name == "SCL";
+    assert_eq!(dip.find(&scl), vec![PinMatch { pin: 2, column: 1 }]);
+    assert_eq!(
+        table(&[dip], &|name| name.starts_with("PC")),
+        vec![
+            "Part   Pin  Name  Column",
+            "MCU      2  PC5   Port",
+            "MCU      3  PC4   Port",
+            "MCU/B    3  PC7   Port",
+        ]
+    );
+}
@@ -0,0 +1,108 @@
+use crate::dip::Dip;
+
+/// Numbering scheme of pins of a dual-row pin header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Numbering {
+    DIP,    // counter-clockwise, as DIP package
+    ZIGZAG, // odd pins on the left row and even pins on the right row
+    ROW,    // left row from top, then right row from top
+}
+
+impl Numbering {
+    /// Returns the name of the numbering in specification.
+    pub fn name(self) -> &'static str {
+        match self {
+            Numbering::DIP => "dip",
+            Numbering::ZIGZAG => "zigzag",
+            Numbering::ROW => "row",
+        }
+    }
+
+    /// Returns the numbering of `name` in specification, if supported.
+    pub fn from_name(name: &str) -> Option<Numbering> {
+        match name {
+            "dip" => Some(Numbering::DIP),
+            "zigzag" => Some(Numbering::ZIGZAG),
+            "row" => Some(Numbering::ROW),
+            _ => None,
+        }
+    }
+
+    /// Returns the difference of numbers of adjacent pins in a row.
+    pub fn stride(self) -> usize {
+        match self {
+            Numbering::ZIGZAG => 2,
+            Numbering::DIP | Numbering::ROW => 1,
+        }
+    }
+
+    /// Returns start and end pin numbers of left and right rows of `pins`
+    /// pins from top to bottom, seen from the top and north.
+    pub(crate) fn rows(self, pins: usize) -> (usize, usize, usize, usize) {
+        let half = pins / 2;
+        match self {
+            Numbering::DIP => (1, half, pins, half + 1),
+            Numbering::ZIGZAG => (1, pins - 1, 2, pins),
+            Numbering::ROW => (1, half, half + 1, pins),
+        }
+    }
+}
+
+impl Dip {
+    /// Returns the pin next to `pin` in the row from `start` to `end`,
+    /// which may be past the end of the row.
+    pub(crate) fn next_pin(&self, pin: usize, start: usize, end: usize) -> usize {
+        let stride = self.numbering.map_or(1, Numbering::stride);
+        if start < end {
+            pin + stride
+        } else {
+            pin.saturating_sub(stride)
+        }
+    }
+
+    /// Returns pin numbers in the row from `start` to `end`.
+    pub(crate) fn row_pins(&self, start: usize, end: usize) -> Vec<usize> {
+        let stride = self.numbering.map_or(1, Numbering::stride);
+        if start <= end {
+            (start..=end).step_by(stride).collect()
+        } else {
+            (end..=start).rev().step_by(stride).collect()
+        }
+    }
+}
+
+#[test]
+fn test_header() {
+    use crate::options::{Direction, PinGap, PrintOptions, Side};
+    use std::str::FromStr;
+    let header = Dip::from_str(
+        r#"
+        name = "J"
+        package = "header"
+        pins = 6
+        1 = "A"
+        2 = "B"
+        3 = "C"
+        4 = "D"
+        5 = "E"
+        6 = "F""#,
+    )
+    .unwrap();
+    assert_eq!(header.numbering, Some(Numbering::ZIGZAG));
+    assert_eq!(header.pin_ranges(Direction::EAST, Side::TOP), (5, 1, 6, 2));
+    assert_eq!(header.row_pins(6, 2), vec![6, 4, 2]);
+    assert_eq!(
+        header.print(&PrintOptions {
+            show_pin: PinGap::PIN1,
+            ..PrintOptions::default()
+        }),
+        vec![
+            "    ___",
+            "A 1|*  |2 B",
+            "C 3| J |4 D",
+            "E 5|___|6 F",
+            "     J",
+        ]
+    );
+    assert_eq!(Numbering::ROW.rows(6), (1, 3, 4, 6));
+}
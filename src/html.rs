@@ -0,0 +1,122 @@
+use crate::dip::Dip;
+use crate::options::AltNames;
+use crate::svg::escape;
+
+const STYLE: &str = "
+body { font-family: sans-serif; }
+section { display: flex; flex-wrap: wrap; align-items: flex-start; gap: 2em; margin-bottom: 2em; }
+h2 { flex-basis: 100%; margin: 0; }
+pre { margin: 0; line-height: 1.2; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.1em 0.6em; text-align: left; }
+td.pin { text-align: right; }
+.overbar { text-decoration: overline; }";
+
+/// Returns lines of a standalone HTML document of `title`, whose body
+/// is `sections` made by `Dip::html`.
+pub fn page(title: &str, sections: &[Vec<String>]) -> Vec<String> {
+    let mut out = vec![
+        String::from("<!DOCTYPE html>"),
+        String::from("<html>"),
+        String::from("<head>"),
+        String::from("<meta charset=\"utf-8\">"),
+        format!("<title>{}</title>", escape(title)),
+        format!("<style>{}", STYLE),
+        String::from("</style>"),
+        String::from("</head>"),
+        String::from("<body>"),
+    ];
+    for section in sections {
+        out.extend(section.iter().cloned());
+    }
+    out.push(String::from("</body>"));
+    out.push(String::from("</html>"));
+
+    out
+}
+
+impl Dip {
+    /// Returns lines of HTML section of the pin table, with `diagram`
+    /// beside it unless it is empty.
+    pub fn html(&self, show_alt: AltNames, overbar: bool, diagram: &[String]) -> Vec<String> {
+        let mut out = vec![
+            String::from("<section>"),
+            format!("<h2>{}</h2>", escape(&self.title)),
+        ];
+        if !diagram.is_empty() {
+            out.push(format!("<pre>{}", escape(&diagram[0])));
+            out.extend(diagram[1..].iter().map(|line| escape(line)));
+            out.push(String::from("</pre>"));
+        }
+
+        let headings = self.column_headings(show_alt);
+        let titles: Vec<String> = headings.iter().map(|heading| escape(heading)).collect();
+        out.push(String::from("<table>"));
+        out.push(format!(
+            "<tr><th>Pin</th><th>{}</th></tr>",
+            titles.join("</th><th>")
+        ));
+        for (n, pin) in self.pins() {
+            let mut cells = Vec::new();
+            for c in 0..headings.len() {
+                cells.push(match pin.signal(c) {
+                    None => String::new(),
+                    Some(signal) if overbar && pin.is_active_low(c) => {
+                        format!("<span class=\"overbar\">{}</span>", escape(signal))
+                    }
+                    Some(_) => escape(pin.names()[c]),
+                });
+            }
+            out.push(format!(
+                "<tr><td class=\"pin\">{}</td><td>{}</td></tr>",
+                self.pin_label(n),
+                cells.join("</td><td>")
+            ));
+        }
+        out.push(String::from("</table>"));
+        out.push(String::from("</section>"));
+
+        out
+    }
+}
+
+#[test]
+fn test_html() {
+    use std::str::FromStr;
+    let dip = Dip::from_str(
+        r#"
+        name = "7400"
+        dip = 2
+        width = 300
+        1 = "PA0, #SS"
+        2 = "GND""#,
+    )
+    .unwrap();
+    let section = dip.html(AltNames::ALL, true, &[String::from("<1>")]);
+    assert_eq!(section[2], "<pre>&lt;1&gt;");
+    assert_eq!(
+        section[5],
+        "<tr><th>Pin</th><th>Name</th><th>Alt 1</th></tr>"
+    );
+    assert_eq!(
+        section[6],
+        "<tr><td class=\"pin\">1</td><td>PA0</td><td><span class=\"overbar\">SS</span></td></tr>"
+    );
+    let dip = Dip::from_str(
+        r#"
+        name = "X"
+        dip = 2
+        width = 300
+        columns = ["Port", "<Func>"]
+        1 = "PA0, D0"
+        2 = "GND""#,
+    )
+    .unwrap();
+    assert_eq!(
+        dip.html(AltNames::ALT1, false, &[])[3],
+        "<tr><th>Pin</th><th>Port</th><th>&lt;Func&gt;</th></tr>"
+    );
+    let page = page("7400", &[section]);
+    assert_eq!(page[4], "<title>7400</title>");
+    assert_eq!(page.last().unwrap(), "</html>");
+}
@@ -0,0 +1,265 @@
+use crate::dip::{Dip, DipWidth, Package};
+use crate::options::{AltNames, Direction, Side};
+use crate::pin::{PinName, PinType};
+use std::cmp::max;
+use std::collections::BTreeMap;
+
+const PITCH: i64 = 254; // 100 mil in 0.01 mm
+const PIN_LENGTH: i64 = 254;
+const CHAR_WIDTH: i64 = 127; // approximate width of a name character
+const FONT: &str = "(effects (font (size 1.27 1.27)))";
+const HIDDEN: &str = "(effects (font (size 1.27 1.27)) hide)";
+
+/// Returns KiCad symbol library of `dips`. Symbols of the same part
+/// name are made unique by suffixes `_2`, `_3` and so on.
+pub fn library(dips: &[Dip]) -> Vec<String> {
+    let mut out = Vec::new();
+    out.push(String::from(
+        "(kicad_symbol_lib (version 20211014) (generator dip)",
+    ));
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for dip in dips {
+        let count = counts.entry(&dip.name).or_insert(0);
+        *count += 1;
+        let symbol = match *count {
+            1 => dip.name.clone(),
+            n => format!("{}_{}", dip.name, n),
+        };
+        out.append(&mut dip.kicad_symbol(&symbol));
+    }
+    out.push(String::from(")"));
+
+    out
+}
+
+impl Dip {
+    /// Returns KiCad symbol named `symbol` of this package.
+    fn kicad_symbol(&self, symbol: &str) -> Vec<String> {
+        let (left, right) = self.symbol_sides();
+        let rows = max(left.len(), right.len());
+        let (lmax, _) = self.max_names_width(&left, AltNames::NONE, false);
+        let (rmax, _) = self.max_names_width(&right, AltNames::NONE, false);
+        let half = grid((lmax + rmax) as i64 * CHAR_WIDTH / 2 + PITCH);
+        let top = (rows as i64 - 1) / 2 * PITCH;
+        let bottom = top - (rows as i64 - 1) * PITCH;
+
+        let mut out = Vec::new();
+        out.push(format!(
+            "  (symbol {} (in_bom yes) (on_board yes)",
+            quote(symbol)
+        ));
+        let properties = [
+            ("Reference", quote("U"), top + PITCH * 2, FONT),
+            ("Value", quote(&self.name), bottom - PITCH * 2, FONT),
+            ("Footprint", quote(&self.footprint()), 0, HIDDEN),
+            ("Datasheet", quote(""), 0, HIDDEN),
+            ("ki_description", quote(&self.title), 0, HIDDEN),
+        ];
+        for (id, (key, value, y, effects)) in properties.iter().enumerate() {
+            out.push(format!(
+                "    (property \"{}\" {} (id {}) (at 0 {} 0) {})",
+                key,
+                value,
+                id,
+                mm(*y),
+                effects
+            ));
+        }
+        out.push(format!("    (symbol {}", quote(&format!("{}_0_1", symbol))));
+        out.push(format!(
+            "      (rectangle (start {} {}) (end {} {}) (stroke (width 0.254) (type default) (color 0 0 0 0)) (fill (type background)))",
+            mm(-half),
+            mm(top + PITCH),
+            mm(half),
+            mm(bottom - PITCH)
+        ));
+        out.push(String::from("    )"));
+        out.push(format!("    (symbol {}", quote(&format!("{}_1_1", symbol))));
+        for (row, &pin) in left.iter().enumerate() {
+            let y = top - row as i64 * PITCH;
+            let number = self.pin_label(pin);
+            let x = -half - PIN_LENGTH;
+            out.append(&mut kicad_pin(&number, &self.pins[&pin], x, y, 0));
+        }
+        for (row, &pin) in right.iter().enumerate() {
+            let y = top - row as i64 * PITCH;
+            let number = self.pin_label(pin);
+            let x = half + PIN_LENGTH;
+            out.append(&mut kicad_pin(&number, &self.pins[&pin], x, y, 180));
+        }
+        out.push(String::from("    )"));
+        out.push(String::from("  )"));
+
+        out
+    }
+
+    /// Returns pins on the left and the right side of the symbol from
+    /// top to bottom. Pins are in DIP order, except that single in-line
+    /// pins are all on the left side and pin header keeps its rows.
+    fn symbol_sides(&self) -> (Vec<usize>, Vec<usize>) {
+        let pins: Vec<usize> = self.pins().map(|(n, _)| n).collect();
+        if self.package.is_inline() {
+            return (pins, Vec::new());
+        }
+        if self.package == Package::HEADER {
+            let (lstart, lend, rstart, rend) = self.pin_ranges(Direction::NORTH, Side::TOP);
+            return (self.row_pins(lstart, lend), self.row_pins(rstart, rend));
+        }
+        let (left, right) = pins.split_at(pins.len().div_ceil(2));
+        (left.to_vec(), right.iter().rev().copied().collect())
+    }
+
+    /// Returns KiCad footprint of this package, or empty if its body
+    /// size is unknown.
+    fn footprint(&self) -> String {
+        match self.package {
+            Package::DIP => {
+                let mil = self.width.mil() as i64;
+                format!("Package_DIP:DIP-{}_W{}mm", self.dip, mm(mil * 254 / 100))
+            }
+            Package::SIP => format!(
+                "Connector_PinHeader_2.54mm:PinHeader_1x{:02}_P2.54mm_Vertical",
+                self.dip
+            ),
+            Package::HEADER if self.width == DipWidth::MIL100 => format!(
+                "Connector_PinHeader_2.54mm:PinHeader_2x{:02}_P2.54mm_Vertical",
+                self.dip / 2
+            ),
+            // PLCC footprints differ by body size and socket
+            Package::ZIP
+            | Package::PLCC
+            | Package::QFP
+            | Package::QFN
+            | Package::PGA
+            | Package::HEADER => String::new(),
+        }
+    }
+}
+
+fn kicad_pin(number: &str, pin: &PinName, x: i64, y: i64, angle: usize) -> Vec<String> {
+    let etype = electrical_type(pin.pin_type());
+    let mut out = Vec::new();
+    out.push(format!(
+        "      (pin {} line (at {} {} {}) (length {})",
+        etype,
+        mm(x),
+        mm(y),
+        angle,
+        mm(PIN_LENGTH)
+    ));
+    out.push(format!(
+        "        (name {} {})",
+        quote(&signal(pin, 0)),
+        FONT
+    ));
+    out.push(format!("        (number \"{}\" {})", number, FONT));
+    for c in 1..pin.names().len() {
+        if !pin.names()[c].is_empty() {
+            out.push(format!(
+                "        (alternate {} {} line)",
+                quote(&signal(pin, c)),
+                etype
+            ));
+        }
+    }
+    out.push(String::from("      )"));
+
+    out
+}
+
+/// Returns `column`-th name of `pin` in KiCad notation, which denotes
+/// overline by `~{}`.
+fn signal(pin: &PinName, column: usize) -> String {
+    match pin.signal(column) {
+        Some(signal) if pin.is_active_low(column) => format!("~{{{}}}", signal),
+        _ => pin.names()[column].to_string(),
+    }
+}
+
+fn electrical_type(pin_type: Option<PinType>) -> &'static str {
+    match pin_type {
+        None => "unspecified",
+        Some(PinType::INPUT) => "input",
+        Some(PinType::OUTPUT) => "output",
+        Some(PinType::BIDIR) => "bidirectional",
+        Some(PinType::POWER) | Some(PinType::GROUND) => "power_in",
+        Some(PinType::OPENCOLLECTOR) => "open_collector",
+        Some(PinType::TRISTATE) => "tri_state",
+        Some(PinType::NOCONNECT) => "no_connect",
+    }
+}
+
+/// Rounds up `v` to 100 mil grid.
+fn grid(v: i64) -> i64 {
+    max(PITCH, (v + PITCH - 1) / PITCH * PITCH)
+}
+
+/// Returns `v` in 0.01 mm as mm.
+fn mm(v: i64) -> String {
+    let sign = if v < 0 { "-" } else { "" };
+    let (int, frac) = (v.abs() / 100, v.abs() % 100);
+    match frac {
+        0 => format!("{}{}", sign, int),
+        f if f % 10 == 0 => format!("{}{}.{}", sign, int, f / 10),
+        f => format!("{}{}.{:02}", sign, int, f),
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[test]
+fn test_kicad_symbol() {
+    use std::str::FromStr;
+    assert_eq!(mm(762), "7.62");
+    assert_eq!(mm(-1270), "-12.7");
+    assert_eq!(mm(-254), "-2.54");
+    assert_eq!(mm(1000), "10");
+
+    let dip = Dip::from_str(
+        r#"
+        name = "ATtiny412"
+        title = "ATtiny412-SS"
+        dip = 4
+        width = 300
+        1 = { names = "VDD", type = "power" }
+        2 = "PA6, #SS"
+        3 = "PA7"
+        4 = { names = ["GND"], type = "ground" }"#,
+    )
+    .unwrap();
+    let lib = library(&[dip]);
+    assert_eq!(
+        lib[0],
+        "(kicad_symbol_lib (version 20211014) (generator dip)"
+    );
+    assert!(lib.contains(&String::from(
+        "    (property \"Footprint\" \"Package_DIP:DIP-4_W7.62mm\" (id 2) (at 0 0 0) (effects (font (size 1.27 1.27)) hide))"
+    )));
+    assert!(lib.contains(&String::from(
+        "      (pin power_in line (at -10.16 0 0) (length 2.54)"
+    )));
+    assert!(lib.contains(&String::from(
+        "      (pin unspecified line (at 10.16 -2.54 180) (length 2.54)"
+    )));
+    assert!(lib.contains(&String::from(
+        "        (alternate \"~{SS}\" unspecified line)"
+    )));
+    assert_eq!(lib.last().unwrap(), ")");
+
+    let dip =
+        || Dip::from_str("name = \"X\"\nplcc = 4\n1 = \"A\"\n2 = \"B\"\n3 = \"C\"\n4 = \"D\"");
+    let lib = library(&[dip().unwrap(), dip().unwrap()]);
+    let symbols: Vec<&String> = lib.iter().filter(|l| l.starts_with("  (symbol ")).collect();
+    assert_eq!(
+        symbols,
+        [
+            "  (symbol \"X\" (in_bom yes) (on_board yes)",
+            "  (symbol \"X_2\" (in_bom yes) (on_board yes)"
+        ]
+    );
+    assert!(lib.contains(&String::from(
+        "    (property \"Footprint\" \"\" (id 2) (at 0 0 0) (effects (font (size 1.27 1.27)) hide))"
+    )));
+}
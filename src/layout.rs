@@ -0,0 +1,45 @@
+use crate::print;
+use std::cmp::max;
+
+/// Returns lines of text drawings made by `Dip::print` laid out in rows
+/// of `columns` drawings, which are separated by `gap` spaces and
+/// aligned at their bottom lines.
+pub fn grid(drawings: &[Vec<String>], columns: usize, gap: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    for (i, row) in drawings.chunks(max(columns, 1)).enumerate() {
+        if i != 0 {
+            out.push(String::new());
+        }
+        let height = row.iter().map(Vec::len).fold(0, max);
+        let widths: Vec<usize> = row
+            .iter()
+            .map(|lines| lines.iter().map(|line| print::width(line)).fold(0, max))
+            .collect();
+        for y in 0..height {
+            let mut line = String::new();
+            for (x, (lines, width)) in row.iter().zip(&widths).enumerate() {
+                if x != 0 {
+                    line.push_str(&print::spaces(gap));
+                }
+                let text = (y + lines.len())
+                    .checked_sub(height)
+                    .map_or("", |y| lines[y].as_str());
+                line.push_str(&print::left(*width, text));
+            }
+            out.push(line.trim_end().to_string());
+        }
+    }
+
+    out
+}
+
+#[test]
+fn test_grid() {
+    let a = vec![String::from(" _ "), String::from("|A|"), String::from(" A")];
+    let b = vec![String::from("|B|"), String::from("B")];
+    assert_eq!(
+        grid(&[a.clone(), b.clone(), a], 2, 2),
+        vec![" _", "|A|  |B|", " A   B", "", " _", "|A|", " A"]
+    );
+    assert_eq!(grid(&[b], 0, 2), vec!["|B|", "B"]);
+}
@@ -0,0 +1,65 @@
+//! DIP (Dual-In-Line Package) pinout drawing with ASCII art.
+//!
+//! ```
+//! use dip::{Dip, Direction, PrintOptions, Style};
+//! use std::str::FromStr;
+//!
+//! let dip = Dip::from_str(
+//!     r##"
+//!     name = "555"
+//!     title = "NE555"
+//!     dip = 8
+//!     width = 300
+//!     1 = "GND"
+//!     2 = "TRIG"
+//!     3 = "OUT"
+//!     4 = "#RESET"
+//!     5 = "CONT"
+//!     6 = "THRES"
+//!     7 = "DISCH"
+//!     8 = "VCC""##,
+//! )
+//! .unwrap();
+//! assert_eq!(dip.pins().count(), 8);
+//! assert_eq!(dip.pin(4).unwrap().name(), "#RESET");
+//! let lines = dip.print(&PrintOptions::default());
+//! assert_eq!(lines[1], "   GND|* 5  |VCC  ");
+//! let lines = dip.print(&PrintOptions {
+//!     dir: Direction::EAST,
+//!     style: Style::UNICODE,
+//!     ..PrintOptions::default()
+//! });
+//! assert_eq!(lines[6], "┌───────┐");
+//! ```
+
+#![allow(clippy::upper_case_acronyms, clippy::too_many_arguments)]
+
+mod color;
+mod diff;
+mod dip;
+mod error;
+pub mod find;
+mod header;
+pub mod html;
+pub mod kicad;
+pub mod layout;
+mod markdown;
+mod options;
+mod pga;
+mod pin;
+mod print;
+mod quad;
+mod sip;
+mod style;
+mod svg;
+mod table;
+
+pub use crate::diff::PinDiff;
+pub use crate::dip::{Dip, DipWidth, Package};
+pub use crate::error::DipError;
+pub use crate::find::PinMatch;
+pub use crate::header::Numbering;
+pub use crate::options::{AltNames, Direction, PinGap, PrintOptions, Side, Style};
+pub use crate::pga::Grid;
+pub use crate::pin::{PinName, PinType};
+pub use crate::quad::{Pin1, Quad};
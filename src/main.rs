@@ -1,39 +1,246 @@
+#![allow(clippy::upper_case_acronyms)]
+
 mod cli;
-mod dip;
-mod pin;
-mod print;
 
-use dip::Dip;
+use cli::{Args, Color, Command, Format, InputFormat};
+use dip::{find, html, kicad, layout, AltNames, Dip, DipError, PrintOptions};
+use std::env;
 use std::fs;
+use std::io::{stdout, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::process::exit;
-use std::str::FromStr;
 
 fn main() {
-    let args_result = cli::parse_args();
-    let content = match &args_result {
+    let args = match cli::parse_args() {
         Err(err) => {
             eprintln!("{}", err);
             exit(1);
         }
-        Ok(args) => match fs::read_to_string(&args.input) {
-            Ok(content) => content,
-            Err(_err) => {
-                eprintln!("can't open {:?}", &args.input);
-                exit(2);
+        Ok(args) => args,
+    };
+
+    if let Some(pattern) = &args.pattern {
+        // a file or directory which can't be read is reported and skipped
+        let mut status = 0;
+        let mut dips = Vec::new();
+        for input in spec_files(&args.inputs) {
+            match input.and_then(|input| load(&input, &args)) {
+                Ok(dip) => dips.push(dip),
+                Err((code, message)) => {
+                    eprint!("{}", message);
+                    status = code;
+                }
             }
-        },
+        }
+        for line in find::table(&dips, &|name| pattern.is_match(name)) {
+            println!("{}", line);
+        }
+        exit(status);
+    }
+    let dips: Vec<Dip> = args.inputs.iter().map(|input| read(input, &args)).collect();
+    if args.command == Command::CONVERT {
+        for line in dips[0].toml() {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    let color = match args.color {
+        Color::ALWAYS => true,
+        Color::NEVER => false,
+        Color::AUTO => stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+    };
+    let options = PrintOptions {
+        dir: args.direction,
+        side: args.side,
+        show_pin: args.show_pin,
+        show_alt: args.show_alt,
+        style: args.style,
+        color,
+        overbar: args.overbar,
     };
-    let args = args_result.unwrap();
 
-    match Dip::from_str(&content) {
-        Err(err) => {
-            eprintln!("{}", err);
-            exit(3);
+    if args.command == Command::DIFF {
+        let (original, replacement) = (&dips[0], &dips[1]);
+        for line in original.diff_table(replacement) {
+            println!("{}", line);
         }
-        Ok(dip) => {
-            for line in dip.print(args.direction, args.side, args.show_pin, args.show_alt) {
+        println!();
+        let combined = original.combine(replacement);
+        for line in combined.print(&PrintOptions {
+            show_alt: AltNames::NONE,
+            color: false,
+            ..options
+        }) {
+            println!("{}", line);
+        }
+        if !original.is_compatible(replacement) {
+            exit(4);
+        }
+        return;
+    }
+    if args.format == Format::JSON {
+        let json = match dips.as_slice() {
+            [dip] => serde_json::to_string_pretty(dip),
+            dips => serde_json::to_string_pretty(dips),
+        };
+        println!("{}", json.unwrap());
+        return;
+    }
+    if let Some(delimiter) = match args.format {
+        Format::CSV => Some(b','),
+        Format::TSV => Some(b'\t'),
+        _ => None,
+    } {
+        for (i, dip) in dips.iter().enumerate() {
+            if i != 0 {
+                println!();
+            }
+            for line in dip.table(delimiter) {
                 println!("{}", line);
             }
         }
+        return;
+    }
+    if args.format == Format::MARKDOWN || args.format == Format::HTML {
+        let mut sections = Vec::new();
+        for dip in &dips {
+            let diagram = if args.diagram {
+                dip.print(&PrintOptions {
+                    color: false,
+                    ..options
+                })
+            } else {
+                Vec::new()
+            };
+            sections.push(match args.format {
+                Format::HTML => dip.html(args.show_alt, args.overbar, &diagram),
+                _ => dip.markdown(args.show_alt, args.overbar, &diagram),
+            });
+        }
+        let lines = match (args.format, dips.as_slice()) {
+            (Format::HTML, [dip]) => html::page(&dip.title, &sections),
+            (Format::HTML, _) => html::page("dip", &sections),
+            _ => sections.join(&String::new()),
+        };
+        for line in lines {
+            println!("{}", line);
+        }
+        return;
+    }
+    if args.format == Format::KICAD {
+        for line in kicad::library(&dips) {
+            println!("{}", line);
+        }
+        return;
+    }
+    if let (Format::TEXT, Some(columns)) = (args.format, args.grid) {
+        let drawings: Vec<Vec<String>> = dips.iter().map(|dip| dip.print(&options)).collect();
+        for line in layout::grid(&drawings, columns, args.gap) {
+            println!("{}", line);
+        }
+        return;
+    }
+    for (i, dip) in dips.iter().enumerate() {
+        if i != 0 && args.format == Format::TEXT {
+            println!();
+        }
+        let lines = match args.format {
+            Format::SVG => dip.svg(
+                args.direction,
+                args.side,
+                args.show_pin,
+                args.show_alt,
+                args.overbar,
+            ),
+            _ => dip.print(&options),
+        };
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Returns `paths` where a directory is replaced by specification files
+/// in it and its subdirectories. A directory which can't be read is
+/// returned as the exit code and diagnostic like `load`.
+fn spec_files(paths: &[PathBuf]) -> Vec<Result<PathBuf, (i32, String)>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(Ok(path.to_path_buf()));
+            continue;
+        }
+        let mut entries: Vec<PathBuf> = match fs::read_dir(path) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|e| e.path())
+                .collect(),
+            Err(_err) => {
+                files.push(Err((2, format!("can't open {:?}\n", path))));
+                continue;
+            }
+        };
+        entries.sort();
+        for entry in spec_files(&entries) {
+            match &entry {
+                Ok(file) => {
+                    if let Some("toml" | "json" | "yaml" | "yml") =
+                        file.extension().and_then(|ext| ext.to_str())
+                    {
+                        files.push(entry);
+                    }
+                }
+                Err(_) => files.push(entry),
+            }
+        }
     }
+
+    files
+}
+
+/// Reads specification or pin table file `input`, exits on error.
+fn read(input: &Path, args: &Args) -> Dip {
+    match load(input, args) {
+        Ok(dip) => dip,
+        Err((code, message)) => {
+            eprint!("{}", message);
+            exit(code);
+        }
+    }
+}
+
+/// Reads specification or pin table file `input`, or returns the exit
+/// code and diagnostics of errors.
+fn load(input: &Path, args: &Args) -> Result<Dip, (i32, String)> {
+    let content = match fs::read_to_string(input) {
+        Ok(content) => content,
+        Err(_err) => return Err((2, format!("can't open {:?}\n", input))),
+    };
+    let format = match args.input_format {
+        InputFormat::AUTO => InputFormat::from_path(input),
+        format => format,
+    };
+    let name = input.file_stem().unwrap_or_default().to_string_lossy();
+    let report = |errors: Vec<DipError>| {
+        let file = input.display().to_string();
+        (3, DipError::report_all(&errors, &file, &content))
+    };
+    let mut dip = match format {
+        InputFormat::JSON => Dip::parse_json(&content),
+        InputFormat::YAML => Dip::parse_yaml(&content),
+        InputFormat::CSV => Dip::parse_table(&content, b',', &name, &args.properties),
+        InputFormat::TSV => Dip::parse_table(&content, b'\t', &name, &args.properties),
+        _ => Dip::parse(&content),
+    }
+    .map_err(report)?;
+    if let Some(variant) = &args.variant {
+        dip = dip.variant(variant).map_err(|err| report(vec![err]))?;
+    }
+    if !args.columns.is_empty() {
+        dip.select_columns(&args.columns)
+            .map_err(|err| report(vec![err]))?;
+    }
+
+    Ok(dip)
 }
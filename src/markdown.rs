@@ -0,0 +1,78 @@
+use crate::dip::Dip;
+use crate::options::AltNames;
+
+impl Dip {
+    /// Returns lines of Markdown pin table of pin numbers and names,
+    /// preceded by `diagram` in a fenced code block unless it is empty.
+    pub fn markdown(&self, show_alt: AltNames, overbar: bool, diagram: &[String]) -> Vec<String> {
+        let mut out = Vec::new();
+        if !diagram.is_empty() {
+            out.push(String::from("```"));
+            out.extend(diagram.iter().cloned());
+            out.push(String::from("```"));
+            out.push(String::new());
+        }
+
+        let headings = self.column_headings(show_alt);
+        let titles: Vec<String> = headings.iter().map(|heading| escape(heading)).collect();
+        out.push(format!("| Pin | {} |", titles.join(" | ")));
+        out.push(format!("| --: |{}", " --- |".repeat(headings.len())));
+        for (n, pin) in self.pins() {
+            let mut names = pin.display_names(overbar);
+            names.resize(headings.len(), String::new());
+            let names: Vec<String> = names.iter().map(|name| escape(name)).collect();
+            out.push(format!("| {} | {} |", self.pin_label(n), names.join(" | ")));
+        }
+
+        out
+    }
+}
+
+/// Escapes characters which have special meaning in a table cell.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('*', "\\*")
+        .replace('_', "\\_")
+}
+
+#[test]
+fn test_markdown() {
+    use std::str::FromStr;
+    let dip = Dip::from_str(
+        r#"
+        name = "7400"
+        dip = 2
+        width = 300
+        1 = "PA0, #SS, A|B"
+        2 = "GND""#,
+    )
+    .unwrap();
+    assert_eq!(
+        dip.markdown(AltNames::ALT1, false, &[]),
+        vec![
+            "| Pin | Name | Alt 1 |",
+            "| --: | --- | --- |",
+            "| 1 | PA0 | #SS |",
+            "| 2 | GND |  |",
+        ]
+    );
+    assert_eq!(
+        dip.markdown(AltNames::ALL, false, &[])[2],
+        "| 1 | PA0 | #SS | A\\|B |"
+    );
+    let dip = Dip::from_str(
+        r#"
+        name = "X"
+        dip = 2
+        width = 300
+        columns = ["Port", "In|Out"]
+        1 = "PA0, D0"
+        2 = "GND""#,
+    )
+    .unwrap();
+    assert_eq!(
+        dip.markdown(AltNames::ALT1, false, &[])[0],
+        "| Pin | Port | In\\|Out |"
+    );
+}
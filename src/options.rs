@@ -0,0 +1,80 @@
+/// Side of the package to be seen.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Side {
+    TOP,
+    BOTTOM,
+}
+
+/// Direction of pin 1 end of the package.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    NORTH,
+    EAST,
+    SOUTH,
+    WEST,
+}
+
+/// Pin number output and its gap to the package body.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PinGap {
+    NONE,
+    PIN1,
+    PIN2,
+}
+
+/// Number of alternate names to be printed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AltNames {
+    NONE,
+    ALT1,
+    ALT2,
+    ALL,
+}
+
+impl AltNames {
+    /// Returns the maximum number of name columns, including the primary
+    /// name.
+    pub fn columns(self) -> usize {
+        match self {
+            AltNames::NONE => 1,
+            AltNames::ALT1 => 2,
+            AltNames::ALT2 => 3,
+            AltNames::ALL => usize::MAX,
+        }
+    }
+}
+
+/// Characters to draw the package body in text output.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Style {
+    ASCII,
+    UNICODE,
+}
+
+/// Options of text drawing by `Dip::print`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PrintOptions {
+    pub dir: Direction,     // direction of pin 1 end
+    pub side: Side,         // side of the package to be seen
+    pub show_pin: PinGap,   // pin number output
+    pub show_alt: AltNames, // alternate names to be printed
+    pub style: Style,       // characters of the package body
+    pub color: bool,        // names colored by ANSI escape sequences
+    pub overbar: bool,      // active-low signals overlined in Unicode style
+}
+
+impl Default for PrintOptions {
+    /// Returns options of north direction top view in ASCII without pin
+    /// numbers, alternate names, colors and overlines.
+    fn default() -> PrintOptions {
+        PrintOptions {
+            dir: Direction::NORTH,
+            side: Side::TOP,
+            show_pin: PinGap::NONE,
+            show_alt: AltNames::NONE,
+            style: Style::ASCII,
+            color: false,
+            overbar: false,
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::color;
+use crate::dip::Dip;
+use crate::options::{AltNames, Direction, PinGap, Side};
+use crate::print;
+use crate::style::Frame;
+use std::cmp::max;
+
+const ROW_LETTERS: &str = "ABCDEFGHJKLMNPRTUVWY"; // without I, O, Q, S, X and Z
+pub(crate) const MAX_ROWS: usize = 420; // rows labeled from A to YY
+
+/// Rows and columns of a pin grid array. A pin is labeled by a row
+/// letter and a column number, like `A1`, and numbered internally in
+/// row-major order from 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grid {
+    pub rows: usize,    // number of rows
+    pub columns: usize, // number of columns
+}
+
+/// Pin numbers of grid positions seen from a side and direction, with
+/// labels of rows and columns.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Cells {
+    pub pins: Vec<Vec<usize>>, // pin numbers of each row from top
+    pub rows: Vec<String>,     // labels of rows from top
+    pub columns: Vec<String>,  // labels of columns from left
+}
+
+impl Grid {
+    /// Returns the label of 0-origin `row`; A to Y, then AA, AB and so on
+    /// up to YY of row `MAX_ROWS - 1`.
+    pub fn row_label(row: usize) -> String {
+        let letters: Vec<char> = ROW_LETTERS.chars().collect();
+        let n = letters.len();
+        if row < n {
+            letters[row].to_string()
+        } else {
+            format!("{}{}", letters[row / n - 1], letters[row % n])
+        }
+    }
+
+    /// Returns the label of pin number `pin`.
+    pub fn label(&self, pin: usize) -> String {
+        let (row, column) = ((pin - 1) / self.columns, (pin - 1) % self.columns);
+        format!("{}{}", Grid::row_label(row), column + 1)
+    }
+
+    /// Returns the pin number of `label`, if it is in the grid.
+    pub fn number(&self, label: &str) -> Option<usize> {
+        let (row, column) = label.split_at(label.find(|c: char| c.is_ascii_digit())?);
+        let row = (0..self.rows).find(|&r| Grid::row_label(r) == row)?;
+        let column = column
+            .parse::<usize>()
+            .ok()
+            .filter(|c| (1..=self.columns).contains(c))?;
+        Some(row * self.columns + column)
+    }
+
+    /// Returns grid positions seen from `side` and `dir`. Row A is at
+    /// the top and column 1 is at the left seen from the top and north.
+    pub(crate) fn cells(&self, dir: Direction, side: Side) -> Cells {
+        let mut cells = Cells {
+            pins: (0..self.rows)
+                .map(|r| (1..=self.columns).map(|c| r * self.columns + c).collect())
+                .collect(),
+            rows: (0..self.rows).map(Grid::row_label).collect(),
+            columns: (1..=self.columns).map(|c| c.to_string()).collect(),
+        };
+        if side == Side::BOTTOM {
+            cells.pins.iter_mut().for_each(|row| row.reverse());
+            cells.columns.reverse();
+        }
+        let turns = match dir {
+            Direction::NORTH => 0,
+            Direction::EAST => 1,
+            Direction::SOUTH => 2,
+            Direction::WEST => 3,
+        };
+        for _ in 0..turns {
+            cells = cells.rotate();
+        }
+
+        cells
+    }
+}
+
+/// Returns true if `key` looks like a grid label, upper case letters
+/// followed by digits.
+pub(crate) fn is_label(key: &str) -> bool {
+    let digits = key.trim_start_matches(|c: char| c.is_ascii_uppercase());
+    digits.len() < key.len() && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+impl Cells {
+    /// Returns cells rotated clockwise by 90 degrees.
+    fn rotate(self) -> Cells {
+        let rows = self.pins.len();
+        let columns = self.columns.len();
+        Cells {
+            pins: (0..columns)
+                .map(|c| (0..rows).rev().map(|r| self.pins[r][c]).collect())
+                .collect(),
+            rows: self.columns,
+            columns: self.rows.into_iter().rev().collect(),
+        }
+    }
+}
+
+impl Dip {
+    pub(crate) fn print_pga(
+        &self,
+        dir: Direction,
+        side: Side,
+        frame: &Frame,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        color: bool,
+        overbar: bool,
+    ) -> Vec<String> {
+        let cells = self.grid.unwrap().cells(dir, side);
+        let lines = self.name_columns(show_alt);
+        let names_width = self
+            .pins()
+            .flat_map(|(_, pin)| pin.display_names(overbar).into_iter().take(lines))
+            .map(|name| print::width(&name))
+            .max()
+            .unwrap_or(0);
+        let label_width = |labels: &[String]| labels.iter().map(|l| l.len()).max().unwrap_or(0);
+        let width = max(names_width, label_width(&cells.columns));
+        let indent = label_width(&cells.rows) + 1;
+        let inner = cells.columns.len() * (width + 1) + 1;
+
+        let mut out = Vec::new();
+        let mut line = print::spaces(indent + 1);
+        for label in &cells.columns {
+            line.push(' ');
+            line.push_str(&print::left(width, label));
+        }
+        out.push(line.trim_end().to_string());
+        out.push(print::spaces(indent) + &frame.horizontal_top.line(inner, false));
+        for (row, label) in cells.pins.iter().zip(&cells.rows) {
+            for l in 0..lines {
+                let mut line = match l {
+                    0 => print::right(indent - 1, label) + " ",
+                    _ => print::spaces(indent),
+                };
+                line.push_str(frame.horizontal_side);
+                for &n in row {
+                    line.push_str(if n == 1 && l == 0 { frame.pin1 } else { " " });
+                    let text = match self.pin(n) {
+                        Some(pin) if l < pin.names().len() => color::paint(
+                            color,
+                            &pin.display_names(overbar)[l],
+                            pin.names()[l],
+                            l,
+                            pin.pin_type(),
+                        ),
+                        _ => String::new(),
+                    };
+                    line.push_str(&print::left(width, &text));
+                }
+                line.push(' ');
+                line.push_str(frame.horizontal_side);
+                out.push(line);
+            }
+        }
+        out.push(print::spaces(indent) + &frame.horizontal_bottom.line(inner, false));
+
+        let center = |text: &str| print::right(indent + (inner + 2 + text.len()) / 2, text);
+        if show_pin != PinGap::NONE {
+            out.push(center(&self.title));
+        } else {
+            out.push(center(&self.name));
+        }
+
+        out
+    }
+}
+
+#[test]
+fn test_pga() {
+    use crate::options::PrintOptions;
+    let grid = Grid {
+        rows: 2,
+        columns: 3,
+    };
+    assert_eq!(grid.label(4), "B1");
+    assert_eq!(grid.number("B3"), Some(6));
+    assert_eq!(grid.number("C1"), None);
+    assert_eq!(grid.number("A4"), None);
+    assert_eq!(Grid::row_label(8), "J");
+    assert_eq!(Grid::row_label(21), "AB");
+    assert!(is_label("AA10"));
+    assert!(!is_label("pin1"));
+    assert!(!is_label("A"));
+    assert_eq!(
+        grid.cells(Direction::EAST, Side::TOP),
+        Cells {
+            pins: vec![vec![4, 1], vec![5, 2], vec![6, 3]],
+            rows: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            columns: vec!["B".to_string(), "A".to_string()],
+        }
+    );
+
+    use std::str::FromStr;
+    let pga = Dip::from_str(
+        r#"
+        name = "X"
+        package = "pga"
+        grid = [2, 2]
+        A1 = "VCC"
+        A2 = "D0"
+        B2 = "GND""#,
+    )
+    .unwrap();
+    assert_eq!(pga.dip, 3);
+    assert_eq!(pga.pin_label(4), "B2");
+    assert_eq!(
+        pga.print(&PrintOptions {
+            side: Side::BOTTOM,
+            ..PrintOptions::default()
+        }),
+        vec![
+            "    2   1",
+            "  +---------+",
+            "A | D0 *VCC |",
+            "B | GND     |",
+            "  +---------+",
+            "       X"
+        ]
+    );
+    let lines = pga.print(&PrintOptions {
+        show_pin: PinGap::PIN1,
+        ..PrintOptions::default()
+    });
+    assert_eq!(lines[lines.len() - 2..], ["  +---------+", "       X"]);
+
+    assert_eq!(Grid::row_label(MAX_ROWS - 1), "YY");
+    let grid = |grid: &str| {
+        Dip::from_str(&format!(
+            "name = \"X\"\npackage = \"pga\"\ngrid = {}\nA1 = \"V\"",
+            grid
+        ))
+        .err()
+        .map(|err| err.to_string())
+    };
+    assert_eq!(grid("[420, 1]"), None);
+    assert_eq!(
+        grid("[500, 1]"),
+        Some("pin grid of 500 pins must be within 420 rows".to_string())
+    );
+    assert_eq!(
+        grid("[100, 100]"),
+        Some("pin grid of 10000 pins must be at most 4096".to_string())
+    );
+}
@@ -1,23 +1,103 @@
-use std::str::FromStr;
+use crate::color;
+use crate::dip;
+use crate::error::DipError;
 use crate::print;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use toml::Value;
 
-#[derive(Debug, PartialEq)]
+const ACTIVE_LOW: char = '#'; // prefix of active-low signal name
+const OVERLINE: char = '\u{0305}'; // combining overline
+
+#[derive(Debug, PartialEq, Serialize)]
 pub struct PinName {
-    names: String, // pin names, separated by comma.
+    names: Vec<String>, // pin name and alternate names
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pin_type: Option<PinType>, // electrical type
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PinType {
+    INPUT,
+    OUTPUT,
+    BIDIR,
+    POWER,
+    GROUND,
+    OPENCOLLECTOR,
+    TRISTATE,
+    NOCONNECT,
 }
 
 impl PinName {
+    pub fn new(names: Vec<String>, pin_type: Option<PinType>) -> PinName {
+        PinName { names, pin_type }
+    }
+
     pub fn names(&self) -> Vec<&str> {
-        self.names.split(',').map(str::trim).collect()
+        self.names.iter().map(String::as_str).collect()
+    }
+
+    pub fn pin_type(&self) -> Option<PinType> {
+        self.pin_type
     }
 
     pub fn name(&self) -> &str {
-        self.names().get(0).unwrap()
+        self.names().first().unwrap()
+    }
+
+    /// Returns true if `column`-th name is an active-low signal.
+    pub fn is_active_low(&self, column: usize) -> bool {
+        self.names()
+            .get(column)
+            .is_some_and(|name| is_active_low(name))
+    }
+
+    /// Returns `column`-th name without active-low prefix, or `None` if
+    /// the pin has no such name.
+    pub fn signal(&self, column: usize) -> Option<&str> {
+        self.names
+            .get(column)
+            .map(|name| name.strip_prefix(ACTIVE_LOW).unwrap_or(name))
     }
 
-    pub fn names_horizontal(&self, names_width: &Vec<usize>, left: bool) -> String {
+    /// Returns names of `columns` in the given order, where a missing
+    /// name is empty. Trailing empty names are dropped.
+    pub(crate) fn select(&self, columns: &[usize]) -> PinName {
+        let mut names: Vec<String> = columns
+            .iter()
+            .map(|&c| self.names.get(c).cloned().unwrap_or_default())
+            .collect();
+        while names.len() > 1 && names.last().is_some_and(String::is_empty) {
+            names.pop();
+        }
+        if names.is_empty() {
+            names.push(String::new());
+        }
+        PinName::new(names, self.pin_type)
+    }
+
+    /// Returns names to print. Active-low signals are overlined instead
+    /// of prefixed when `overbar` is true.
+    pub fn display_names(&self, overbar: bool) -> Vec<String> {
+        (0..self.names.len())
+            .map(|c| match self.signal(c) {
+                Some(signal) if overbar && self.is_active_low(c) => overline(signal),
+                _ => self.names[c].clone(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn names_horizontal(
+        &self,
+        names_width: &[usize],
+        left: bool,
+        color: bool,
+        overbar: bool,
+    ) -> String {
         let column = names_width.len();
         let names: Vec<&str> = self.names();
+        let texts = self.display_names(overbar);
         let mut line = String::new();
         for c in 0..column {
             if c != 0 {
@@ -28,11 +108,13 @@ impl PinName {
                 if n >= names.len() {
                     line.push_str(&print::spaces(names_width[n]));
                 } else {
-                    line.push_str(&print::right(names_width[n], names[n]));
+                    let name = color::paint(color, &texts[n], names[n], n, self.pin_type);
+                    line.push_str(&print::right(names_width[n], &name));
                 }
             } else {
                 if c < names.len() {
-                    line.push_str(&print::left(names_width[c], names[c]));
+                    let name = color::paint(color, &texts[c], names[c], c, self.pin_type);
+                    line.push_str(&print::left(names_width[c], &name));
                 }
             }
         }
@@ -40,9 +122,16 @@ impl PinName {
         line
     }
 
-    pub fn names_vertical(&self, names_width: &Vec<usize>, top: bool) -> Vec<String> {
+    pub(crate) fn names_vertical(
+        &self,
+        names_width: &[usize],
+        top: bool,
+        color: bool,
+        overbar: bool,
+    ) -> Vec<String> {
         let column = names_width.len();
         let names: Vec<&str> = self.names();
+        let texts = self.display_names(overbar);
         let mut out: Vec<String> = Vec::new();
         for c in 0..column {
             if c != 0 {
@@ -50,11 +139,29 @@ impl PinName {
             }
             if top {
                 let n = column - 1 - c;
-                let text = if n < names.len() { names[n] } else { "" };
-                out.append(&mut print::bottom(names_width[n], text));
+                let (name, text) = if n < names.len() {
+                    (names[n], texts[n].as_str())
+                } else {
+                    ("", "")
+                };
+                let chars = print::bottom(names_width[n], text);
+                out.extend(
+                    chars
+                        .iter()
+                        .map(|c| color::paint(color, c, name, n, self.pin_type)),
+                );
             } else {
-                let text = if c < names.len() { names[c] } else { "" };
-                out.append(&mut print::top(names_width[c], text));
+                let (name, text) = if c < names.len() {
+                    (names[c], texts[c].as_str())
+                } else {
+                    ("", "")
+                };
+                let chars = print::top(names_width[c], text);
+                out.extend(
+                    chars
+                        .iter()
+                        .map(|ch| color::paint(color, ch, name, c, self.pin_type)),
+                );
             }
         }
 
@@ -63,10 +170,116 @@ impl PinName {
 }
 
 impl FromStr for PinName {
-    type Err = String;
+    type Err = DipError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(PinName {
-            names: s.to_string(),
+            names: s.split(',').map(str::trim).map(String::from).collect(),
+            pin_type: None,
+        })
+    }
+}
+
+impl FromStr for PinType {
+    type Err = DipError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(PinType::INPUT),
+            "output" => Ok(PinType::OUTPUT),
+            "bidir" => Ok(PinType::BIDIR),
+            "power" => Ok(PinType::POWER),
+            "ground" => Ok(PinType::GROUND),
+            "open-collector" => Ok(PinType::OPENCOLLECTOR),
+            "tri-state" => Ok(PinType::TRISTATE),
+            "no-connect" => Ok(PinType::NOCONNECT),
+            _ => Err(DipError::UnknownPinType {
+                key: "type".to_string(),
+                pin_type: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PinName {
+    /// Deserializes comma separated names or a table of `names` and
+    /// optional `type`, same as a pin of the specification.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v = Value::deserialize(deserializer)?;
+        dip::pin_to_result("pin", 1, &v, None, &[]).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for PinType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PinType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        PinType::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for PinType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PinType::INPUT => "input",
+            PinType::OUTPUT => "output",
+            PinType::BIDIR => "bidir",
+            PinType::POWER => "power",
+            PinType::GROUND => "ground",
+            PinType::OPENCOLLECTOR => "open-collector",
+            PinType::TRISTATE => "tri-state",
+            PinType::NOCONNECT => "no-connect",
         })
     }
 }
+
+/// Returns true if `name` is an active-low signal.
+pub fn is_active_low(name: &str) -> bool {
+    name.starts_with(ACTIVE_LOW)
+}
+
+/// Returns `text` with combining overline on each character.
+pub fn overline(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        out.push(c);
+        out.push(OVERLINE);
+    }
+
+    out
+}
+
+#[test]
+fn test_active_low() {
+    let pin = PinName::from_str("PC6, D22, #RESET").unwrap();
+    assert!(!pin.is_active_low(0));
+    assert!(pin.is_active_low(2));
+    assert!(!pin.is_active_low(3));
+    assert_eq!(pin.signal(2), Some("RESET"));
+    assert_eq!(pin.signal(1), Some("D22"));
+    assert_eq!(pin.signal(3), None);
+    assert_eq!(
+        pin.display_names(true),
+        vec!["PC6", "D22", "R\u{305}E\u{305}S\u{305}E\u{305}T\u{305}"]
+    );
+    assert_eq!(pin.display_names(false), vec!["PC6", "D22", "#RESET"]);
+}
+
+#[test]
+fn test_pin_type() {
+    assert_eq!(
+        PinType::from_str("open-collector"),
+        Ok(PinType::OPENCOLLECTOR)
+    );
+    assert_eq!(PinType::TRISTATE.to_string(), "tri-state");
+    assert_eq!(
+        PinType::from_str("analog"),
+        Err(DipError::UnknownPinType {
+            key: "type".to_string(),
+            pin_type: "analog".to_string()
+        })
+    );
+}
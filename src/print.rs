@@ -2,8 +2,9 @@ use unicode_segmentation::UnicodeSegmentation;
 
 pub fn left(width: usize, text: &str) -> String {
     let mut out = String::from(text);
-    if width >= text.len() {
-        out.push_str(&spaces(width - text.len()));
+    let len = self::width(text);
+    if width >= len {
+        out.push_str(&spaces(width - len));
     }
 
     out
@@ -11,8 +12,9 @@ pub fn left(width: usize, text: &str) -> String {
 
 pub fn right(width: usize, text: &str) -> String {
     let mut out = String::new();
-    if width >= text.len() {
-        out.push_str(&spaces(width - text.len()));
+    let len = self::width(text);
+    if width >= len {
+        out.push_str(&spaces(width - len));
     }
     out.push_str(text);
 
@@ -23,6 +25,26 @@ pub fn right(width: usize, text: &str) -> String {
 fn test_print_left_right() {
     assert_eq!(left(5, "AB"), "AB   ");
     assert_eq!(right(5, "AB"), "   AB");
+    assert_eq!(left(5, "\x1b[31mAB\x1b[0m"), "\x1b[31mAB\x1b[0m   ");
+    assert_eq!(right(5, "\x1b[31mAB\x1b[0m"), "   \x1b[31mAB\x1b[0m");
+}
+
+/// Returns the number of characters of `text`, excluding ANSI escape
+/// sequences.
+pub fn width(text: &str) -> usize {
+    let mut plain = String::new();
+    let mut escape = false;
+    for c in text.chars() {
+        if escape {
+            escape = c != 'm';
+        } else if c == '\x1b' {
+            escape = true;
+        } else {
+            plain.push(c);
+        }
+    }
+
+    plain.graphemes(true).count()
 }
 
 pub fn top(height: usize, text: &str) -> Vec<String> {
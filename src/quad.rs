@@ -0,0 +1,285 @@
+use crate::dip::Dip;
+use crate::options::{AltNames, Direction, PinGap, Side};
+use crate::print;
+use crate::style::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Arrangement of pins of a quad package, such as PLCC, QFP and QFN.
+/// Pins are numbered counter-clockwise seen from the top.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quad {
+    pub vertical: usize,   // pins on each of left and right edges
+    pub horizontal: usize, // pins on each of top and bottom edges
+    pub pin1: Pin1,        // position of pin 1
+}
+
+/// Position of pin 1 of a quad package.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pin1 {
+    LEFTTOP,   // top end of left edge, as QFP
+    TOPCENTER, // center of top edge, as PLCC
+}
+
+impl Pin1 {
+    /// Returns the name of the position in specification.
+    pub fn name(self) -> &'static str {
+        match self {
+            Pin1::LEFTTOP => "left-top",
+            Pin1::TOPCENTER => "top-center",
+        }
+    }
+
+    /// Returns the position of `name` in specification, if supported.
+    pub fn from_name(name: &str) -> Option<Pin1> {
+        match name {
+            "left-top" => Some(Pin1::LEFTTOP),
+            "top-center" => Some(Pin1::TOPCENTER),
+            _ => None,
+        }
+    }
+}
+
+/// Pin numbers on each edge of a quad package.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Edges {
+    pub left: Vec<usize>,   // top to bottom
+    pub bottom: Vec<usize>, // left to right
+    pub right: Vec<usize>,  // top to bottom
+    pub top: Vec<usize>,    // left to right
+}
+
+impl Quad {
+    /// Returns the total number of pins.
+    pub fn pins(&self) -> usize {
+        (self.vertical + self.horizontal) * 2
+    }
+
+    /// Returns pins on each edge seen from `side` and `dir`.
+    pub(crate) fn edges(&self, dir: Direction, side: Side) -> Edges {
+        let (v, h, n) = (self.vertical, self.horizontal, self.pins());
+        let offset = match self.pin1 {
+            Pin1::LEFTTOP => 0,
+            Pin1::TOPCENTER => h.div_ceil(2),
+        };
+        // counter-clockwise from the top end of left edge
+        let ccw: Vec<usize> = (0..n).map(|i| (offset + i) % n + 1).collect();
+        let mut edges = Edges {
+            left: ccw[..v].to_vec(),
+            bottom: ccw[v..v + h].to_vec(),
+            right: ccw[v + h..v * 2 + h].iter().rev().copied().collect(),
+            top: ccw[v * 2 + h..].iter().rev().copied().collect(),
+        };
+        if side == Side::BOTTOM {
+            edges = Edges {
+                left: edges.right,
+                bottom: edges.bottom.into_iter().rev().collect(),
+                right: edges.left,
+                top: edges.top.into_iter().rev().collect(),
+            };
+        }
+        let turns = match dir {
+            Direction::NORTH => 0,
+            Direction::EAST => 1,
+            Direction::SOUTH => 2,
+            Direction::WEST => 3,
+        };
+        for _ in 0..turns {
+            edges = edges.rotate();
+        }
+
+        edges
+    }
+}
+
+impl Edges {
+    /// Returns edges rotated clockwise by 90 degrees.
+    fn rotate(self) -> Edges {
+        Edges {
+            left: self.bottom,
+            bottom: self.right.into_iter().rev().collect(),
+            right: self.top,
+            top: self.left.into_iter().rev().collect(),
+        }
+    }
+
+    /// Returns the line and column of pin 1 mark inside of a body of
+    /// `width` columns.
+    fn pin1(&self, width: usize) -> (usize, usize) {
+        let at = |pins: &[usize]| pins.iter().position(|&pin| pin == 1);
+        if let Some(i) = at(&self.left) {
+            (i + 1, 0)
+        } else if let Some(i) = at(&self.right) {
+            (i + 1, width - 1)
+        } else if let Some(i) = at(&self.top) {
+            (0, i * 2 + 1)
+        } else {
+            (self.left.len() + 1, at(&self.bottom).unwrap_or(0) * 2 + 1)
+        }
+    }
+}
+
+impl Dip {
+    pub(crate) fn print_quad(
+        &self,
+        dir: Direction,
+        side: Side,
+        frame: &Frame,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        color: bool,
+        overbar: bool,
+    ) -> Vec<String> {
+        let edges = self.quad.unwrap().edges(dir, side);
+        let (lmax, lmaxes) = self.max_names_width(&edges.left, show_alt, overbar);
+        let (rmax, rmaxes) = self.max_names_width(&edges.right, show_alt, overbar);
+        let pin_width = |pins: &[usize]| match show_pin {
+            PinGap::PIN2 => self.max_pins_width(pins) + 2,
+            PinGap::PIN1 => self.max_pins_width(pins) + 1,
+            PinGap::NONE => 0,
+        };
+        let (lpin_width, rpin_width) = (pin_width(&edges.left), pin_width(&edges.right));
+
+        let indent = lmax + lpin_width;
+        let width = edges.top.len() * 2 + 1;
+        let height = edges.left.len() + 2;
+        let mut out = Vec::new();
+        let mut pins = Vec::new();
+        self.print_pin_list_vertical(
+            &edges.top, show_pin, show_alt, color, overbar, true, &mut pins,
+        );
+        for line in pins {
+            out.push(print::spaces(indent + 1) + &line);
+        }
+        out.push(print::spaces(indent) + &frame.horizontal_top.line(width, false));
+
+        let mark = edges.pin1(width);
+        let name_chars = self.name_chars();
+        let name_left = width.saturating_sub(name_chars.len()) / 2;
+        for l in 0..height {
+            let row = (l >= 1 && l <= edges.left.len()).then(|| l - 1);
+            let mut line = match row {
+                None => print::spaces(indent),
+                Some(r) => {
+                    let pin = edges.left[r];
+                    let names = self.pins[&pin].names_horizontal(&lmaxes, true, color, overbar);
+                    let mut line = print::right(lmax, &names);
+                    if show_pin != PinGap::NONE {
+                        line.push_str(&print::right(lpin_width, &pin.to_string()));
+                    }
+                    line
+                }
+            };
+            line.push_str(frame.horizontal_side);
+            for c in 0..width {
+                let name = (l == height / 2 && c >= name_left)
+                    .then(|| name_chars.get(c - name_left))
+                    .flatten();
+                match name {
+                    _ if (l, c) == mark => line.push_str(frame.pin1),
+                    Some(ch) => line.push_str(ch),
+                    None => line.push(' '),
+                }
+            }
+            line.push_str(frame.horizontal_side);
+            if let Some(r) = row {
+                let pin = edges.right[r];
+                if show_pin != PinGap::NONE {
+                    line.push_str(&print::left(rpin_width, &pin.to_string()));
+                }
+                let names = self.pins[&pin].names_horizontal(&rmaxes, false, color, overbar);
+                line.push_str(&print::left(rmax, &names));
+            }
+            out.push(line);
+        }
+
+        out.push(print::spaces(indent) + &frame.horizontal_bottom.line(width, false));
+        let mut pins = Vec::new();
+        self.print_pin_list_vertical(
+            &edges.bottom,
+            show_pin,
+            show_alt,
+            color,
+            overbar,
+            false,
+            &mut pins,
+        );
+        for line in pins {
+            out.push(print::spaces(indent + 1) + &line);
+        }
+
+        if show_pin != PinGap::NONE {
+            let width = indent + (width + 2 + self.title.graphemes(true).count()) / 2;
+            out.push(print::right(width, &self.title));
+        }
+
+        out
+    }
+}
+
+#[test]
+fn test_quad_edges() {
+    let plcc = Quad {
+        vertical: 3,
+        horizontal: 3,
+        pin1: Pin1::TOPCENTER,
+    };
+    assert_eq!(
+        plcc.edges(Direction::NORTH, Side::TOP),
+        Edges {
+            left: vec![3, 4, 5],
+            bottom: vec![6, 7, 8],
+            right: vec![11, 10, 9],
+            top: vec![2, 1, 12],
+        }
+    );
+    assert_eq!(
+        plcc.edges(Direction::EAST, Side::BOTTOM),
+        Edges {
+            left: vec![8, 7, 6],
+            bottom: vec![5, 4, 3],
+            right: vec![12, 1, 2],
+            top: vec![9, 10, 11],
+        }
+    );
+}
+
+#[test]
+fn test_print_quad() {
+    use crate::options::PrintOptions;
+    use std::str::FromStr;
+    let qfn = Dip::from_str(
+        r#"
+        name = "Q"
+        qfn = 8
+        1 = "A"
+        2 = "B"
+        3 = "C"
+        4 = "D"
+        5 = "E"
+        6 = "F"
+        7 = "G"
+        8 = "H""#,
+    )
+    .unwrap();
+    assert_eq!(
+        qfn.quad,
+        Some(Quad {
+            vertical: 2,
+            horizontal: 2,
+            pin1: Pin1::LEFTTOP
+        })
+    );
+    assert_eq!(
+        qfn.print(&PrintOptions::default()),
+        vec![
+            "   H G",
+            " +-----+",
+            " |     |",
+            "A|*    |F",
+            "B|  Q  |E",
+            " |     |",
+            " +-----+",
+            "   C D"
+        ]
+    );
+}
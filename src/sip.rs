@@ -0,0 +1,267 @@
+use crate::dip::{pin_list, Dip, Package};
+use crate::options::{AltNames, Direction, PinGap, Side};
+use crate::print;
+use crate::style::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+
+const BODY_WIDTH: usize = 3; // inner width of north/south package
+const BODY_HEIGHT: usize = 4; // height of east/west package including edges
+
+impl Dip {
+    /// Returns start and end pin numbers of the row of SIP package seen
+    /// from `side` and `dir`, and true if names are on the left (top) of
+    /// the row. Odd pins of ZIP package are on that side.
+    pub(crate) fn sip_range(&self, dir: Direction, side: Side) -> (usize, usize, bool) {
+        let (start, end, first) = match dir {
+            Direction::NORTH => (1, self.dip, true),
+            Direction::SOUTH => (self.dip, 1, false),
+            Direction::EAST => (self.dip, 1, true),
+            Direction::WEST => (1, self.dip, false),
+        };
+        (start, end, first == (side == Side::TOP))
+    }
+
+    /// Returns pins from `start` to `end` on the left (top) and the right
+    /// (bottom) side of the row, where `first` is the side of pin names
+    /// of SIP package and odd pins of ZIP package.
+    pub(crate) fn sip_sides(
+        &self,
+        start: usize,
+        end: usize,
+        first: bool,
+    ) -> (Vec<usize>, Vec<usize>) {
+        pin_list(start, end).into_iter().partition(|pin| {
+            let odd = self.package != Package::ZIP || pin % 2 == 1;
+            odd == first
+        })
+    }
+
+    pub(crate) fn print_sip(
+        &self,
+        dir: Direction,
+        side: Side,
+        frame: &Frame,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        color: bool,
+        overbar: bool,
+    ) -> Vec<String> {
+        let (start, end, first) = self.sip_range(dir, side);
+        match dir {
+            Direction::NORTH | Direction::SOUTH => self
+                .print_sip_vertical(frame, show_pin, show_alt, color, overbar, start, end, first),
+            Direction::EAST | Direction::WEST => self
+                .print_sip_horizontal(frame, show_pin, show_alt, color, overbar, start, end, first),
+        }
+    }
+
+    fn print_sip_vertical(
+        &self,
+        frame: &Frame,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        color: bool,
+        overbar: bool,
+        start: usize,
+        end: usize,
+        first: bool,
+    ) -> Vec<String> {
+        let (lpins, rpins) = self.sip_sides(start, end, first);
+        let (lmax, lmaxes) = self.max_names_width(&lpins, show_alt, overbar);
+        let (rmax, rmaxes) = self.max_names_width(&rpins, show_alt, overbar);
+        let pin_width = |pins: &[usize]| match show_pin {
+            _ if pins.is_empty() => 0,
+            PinGap::PIN2 => self.max_pins_width(pins) + 2,
+            PinGap::PIN1 => self.max_pins_width(pins) + 1,
+            PinGap::NONE => 0,
+        };
+        let (lpin_width, rpin_width) = (pin_width(&lpins), pin_width(&rpins));
+
+        let indent = lmax + lpin_width + 1;
+        let top = &frame.vertical_top;
+        let mut out = Vec::new();
+        let mut line = print::spaces(indent - top.left.graphemes(true).count());
+        line.push_str(&top.line(BODY_WIDTH, false));
+        out.push(line);
+
+        let rows = self.dip;
+        let mark = if lpins.contains(&1) {
+            0
+        } else {
+            BODY_WIDTH - 1
+        };
+        let name_chars = self.name_chars();
+        let name_start = rows.saturating_sub(name_chars.len()) / 2 + 1;
+        let mut pin = start;
+        for pos in 1..=rows {
+            let left = lpins.contains(&pin);
+            let mut line = String::new();
+            if left {
+                let names = self.pins[&pin].names_horizontal(&lmaxes, true, color, overbar);
+                line.push_str(&print::right(lmax, &names));
+                if show_pin != PinGap::NONE {
+                    line.push_str(&print::right(lpin_width, &pin.to_string()));
+                }
+            } else {
+                line.push_str(&print::spaces(indent - 1));
+            }
+
+            let spc = if pos == rows {
+                frame.vertical_last
+            } else {
+                " "
+            };
+            line.push_str(frame.vertical_side);
+            for c in 0..BODY_WIDTH {
+                let name = (pos >= name_start && c == BODY_WIDTH / 2)
+                    .then(|| name_chars.get(pos - name_start))
+                    .flatten();
+                match name {
+                    _ if pin == 1 && c == mark => line.push_str(frame.pin1),
+                    Some(ch) => line.push_str(ch),
+                    None => line.push_str(spc),
+                }
+            }
+            line.push_str(frame.vertical_side);
+
+            if !left {
+                if show_pin != PinGap::NONE {
+                    line.push_str(&print::left(rpin_width, &pin.to_string()));
+                }
+                let names = self.pins[&pin].names_horizontal(&rmaxes, false, color, overbar);
+                line.push_str(&print::left(rmax, &names));
+            }
+            out.push(line);
+
+            pin = self.next_pin(pin, start, end);
+        }
+        if let Some(bottom) = &frame.vertical_bottom {
+            let mut line = print::spaces(indent - bottom.left.graphemes(true).count());
+            line.push_str(&bottom.line(BODY_WIDTH, false));
+            out.push(line);
+        }
+        if show_pin != PinGap::NONE {
+            let width = indent + (BODY_WIDTH + self.title.len()) / 2;
+            out.push(print::right(width, &self.title));
+        }
+
+        out
+    }
+
+    fn print_sip_horizontal(
+        &self,
+        frame: &Frame,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        color: bool,
+        overbar: bool,
+        start: usize,
+        end: usize,
+        first: bool,
+    ) -> Vec<String> {
+        let (tpins, bpins) = self.sip_sides(start, end, first);
+        // ZIP pins are staggered by a column
+        let step = if self.package == Package::ZIP { 1 } else { 2 };
+        let width = (self.dip - 1) * step + 1;
+        let indent = |pins: &[usize]| print::spaces(usize::from(pins.first() != Some(&start)));
+        let mut out = Vec::new();
+        if !tpins.is_empty() {
+            let mut pins = Vec::new();
+            self.print_pin_list_vertical(
+                &tpins, show_pin, show_alt, color, overbar, true, &mut pins,
+            );
+            out.extend(pins.into_iter().map(|line| indent(&tpins) + &line));
+        }
+        out.push(frame.horizontal_top.line(width, false));
+        let name_chars = self.name_chars();
+        let name_left = width.saturating_sub(name_chars.len()) / 2;
+        let (mark_line, name_line) = if tpins.contains(&1) { (0, 1) } else { (1, 0) };
+        let mark = pin_list(start, end)
+            .iter()
+            .position(|&pin| pin == 1)
+            .unwrap()
+            * step;
+        for l in 0..BODY_HEIGHT - 2 {
+            let mut line = String::from(frame.horizontal_side);
+            for c in 0..width {
+                let name = (l == name_line && c >= name_left)
+                    .then(|| name_chars.get(c - name_left))
+                    .flatten();
+                match name {
+                    _ if l == mark_line && c == mark => line.push_str(frame.pin1),
+                    Some(ch) => line.push_str(ch),
+                    None => line.push(' '),
+                }
+            }
+            line.push_str(frame.horizontal_side);
+            out.push(line);
+        }
+        out.push(frame.horizontal_bottom.line(width, false));
+        if !bpins.is_empty() {
+            let mut pins = Vec::new();
+            self.print_pin_list_vertical(
+                &bpins, show_pin, show_alt, color, overbar, false, &mut pins,
+            );
+            out.extend(pins.into_iter().map(|line| indent(&bpins) + &line));
+        }
+
+        if show_pin != PinGap::NONE {
+            let width = (width + 2 + self.title.len()) / 2;
+            out.push(print::right(width, &self.title));
+        }
+
+        out
+    }
+}
+
+#[test]
+fn test_print_sip() {
+    use crate::options::PrintOptions;
+    use std::str::FromStr;
+    let sip = Dip::from_str(
+        r#"
+        name = "RN"
+        package = "sip"
+        pins = 3
+        1 = "COM"
+        2 = "R1"
+        3 = "R2""#,
+    )
+    .unwrap();
+    let print = |dir, side| {
+        sip.print(&PrintOptions {
+            dir,
+            side,
+            ..PrintOptions::default()
+        })
+    };
+    assert_eq!(
+        print(Direction::NORTH, Side::TOP),
+        vec!["    ___", "COM|*R |", " R1| N |", " R2|___|"]
+    );
+    assert_eq!(
+        print(Direction::NORTH, Side::BOTTOM),
+        vec![" ___", "| R*|COM", "| N |R1 ", "|___|R2 "]
+    );
+    assert_eq!(
+        print(Direction::EAST, Side::TOP),
+        vec!["     C", " R R O", " 2 1 M", "+-----+", "|    *|", "| RN  |", "+-----+"]
+    );
+
+    let zip =
+        Dip::from_str("name = \"Z\"\nzip = 4\n1 = \"A\"\n2 = \"B\"\n3 = \"C\"\n4 = \"D\"").unwrap();
+    let print = |dir| {
+        zip.print(&PrintOptions {
+            dir,
+            ..PrintOptions::default()
+        })
+    };
+    assert_eq!(
+        print(Direction::NORTH),
+        vec!["  ___", "A|*  |", " | Z |B", "C|   |", " |___|D"]
+    );
+    assert_eq!(
+        print(Direction::WEST),
+        vec!["  B D", "+----+", "| Z  |", "|*   |", "+----+", " A C"]
+    );
+}
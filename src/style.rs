@@ -0,0 +1,120 @@
+use crate::options::Style;
+
+/// Characters of a top or bottom edge of a package body.
+pub struct Edge {
+    pub left: &'static str,       // left corner
+    pub line: &'static str,       // edge line
+    pub notch: [&'static str; 3], // pin 1 notch at the center of the edge
+    pub right: &'static str,      // right corner
+}
+
+/// Characters to draw a package body.
+pub struct Frame {
+    pub vertical_top: Edge,            // top edge of north/south package
+    pub vertical_bottom: Option<Edge>, // bottom edge of north/south package
+    pub vertical_side: &'static str,   // left/right edge of north/south package
+    pub vertical_last: &'static str,   // fill of the last pin row
+    pub horizontal_top: Edge,          // top edge of east/west package
+    pub horizontal_bottom: Edge,       // bottom edge of east/west package
+    pub horizontal_side: &'static str, // left/right edge of east/west package
+    pub notch_left: &'static str,      // pin 1 notch on left edge
+    pub notch_right: &'static str,     // pin 1 notch on right edge
+    pub pin1: &'static str,            // pin 1 mark
+}
+
+const ASCII: Frame = Frame {
+    vertical_top: Edge {
+        left: "",
+        line: "_",
+        notch: ["_", "_", "_"],
+        right: "",
+    },
+    vertical_bottom: None,
+    vertical_side: "|",
+    vertical_last: "_",
+    horizontal_top: Edge {
+        left: "+",
+        line: "-",
+        notch: ["-", "-", "-"],
+        right: "+",
+    },
+    horizontal_bottom: Edge {
+        left: "+",
+        line: "-",
+        notch: ["-", "-", "-"],
+        right: "+",
+    },
+    horizontal_side: "|",
+    notch_left: "|",
+    notch_right: "|",
+    pin1: "*",
+};
+
+const UNICODE: Frame = Frame {
+    vertical_top: Edge {
+        left: "┌",
+        line: "─",
+        notch: ["╮", " ", "╭"],
+        right: "┐",
+    },
+    vertical_bottom: Some(Edge {
+        left: "└",
+        line: "─",
+        notch: ["╯", " ", "╰"],
+        right: "┘",
+    }),
+    vertical_side: "│",
+    vertical_last: " ",
+    horizontal_top: Edge {
+        left: "┌",
+        line: "─",
+        notch: ["╮", " ", "╭"],
+        right: "┐",
+    },
+    horizontal_bottom: Edge {
+        left: "└",
+        line: "─",
+        notch: ["╯", " ", "╰"],
+        right: "┘",
+    },
+    horizontal_side: "│",
+    notch_left: "◗",
+    notch_right: "◖",
+    pin1: "●",
+};
+
+impl Style {
+    pub(crate) fn frame(self) -> &'static Frame {
+        match self {
+            Style::ASCII => &ASCII,
+            Style::UNICODE => &UNICODE,
+        }
+    }
+}
+
+impl Edge {
+    /// Returns an edge of `width` characters between corners, with a
+    /// notch at the center if `notch` is true.
+    pub fn line(&self, width: usize, notch: bool) -> String {
+        let center = width / 2;
+        let mut line = String::from(self.left);
+        for c in 0..width {
+            if notch && c + 1 >= center && c <= center + 1 {
+                line.push_str(self.notch[c + 1 - center]);
+            } else {
+                line.push_str(self.line);
+            }
+        }
+        line.push_str(self.right);
+
+        line
+    }
+}
+
+#[test]
+fn test_edge_line() {
+    assert_eq!(ASCII.vertical_top.line(5, true), "_____");
+    assert_eq!(UNICODE.vertical_top.line(5, false), "┌─────┐");
+    assert_eq!(UNICODE.vertical_top.line(5, true), "┌─╮ ╭─┐");
+    assert_eq!(UNICODE.horizontal_bottom.line(7, true), "└──╯ ╰──┘");
+}
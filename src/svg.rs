@@ -0,0 +1,949 @@
+use crate::dip::{Dip, Package};
+use crate::options::{AltNames, Direction, PinGap, Side};
+use crate::pin::{PinName, PinType};
+use crate::print;
+use std::cmp::{max, min};
+
+const CHAR_WIDTH: usize = 10; // width of a character cell
+const LINE_HEIGHT: usize = 20; // height of a line, equals to 100 mil pin pitch
+const FONT_SIZE: usize = 16;
+const MARGIN: usize = 10;
+const STUB_LENGTH: usize = 10; // length of a pin stub
+const STUB_WIDTH: usize = 6; // width of a pin stub
+
+impl Dip {
+    /// Returns lines of SVG document of the package.
+    pub fn svg(
+        &self,
+        dir: Direction,
+        side: Side,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+    ) -> Vec<String> {
+        let mut canvas = Canvas::new();
+        if self.package.is_inline() {
+            let (start, end, first) = self.sip_range(dir, side);
+            match dir {
+                Direction::NORTH | Direction::SOUTH => self.svg_sip_vertical(
+                    &mut canvas,
+                    show_pin,
+                    show_alt,
+                    overbar,
+                    start,
+                    end,
+                    first,
+                ),
+                Direction::EAST | Direction::WEST => self.svg_sip_horizontal(
+                    &mut canvas,
+                    show_pin,
+                    show_alt,
+                    overbar,
+                    start,
+                    end,
+                    first,
+                ),
+            }
+            return canvas.finish();
+        }
+        if self.package == Package::PGA {
+            self.svg_pga(&mut canvas, dir, side, show_pin, show_alt, overbar);
+            return canvas.finish();
+        }
+        if self.package.is_quad() {
+            self.svg_quad(&mut canvas, dir, side, show_pin, show_alt, overbar);
+            return canvas.finish();
+        }
+        let (start1, end1, start2, end2) = self.pin_ranges(dir, side);
+        match dir {
+            Direction::NORTH | Direction::SOUTH => self.svg_vertical(
+                &mut canvas,
+                dir,
+                show_pin,
+                show_alt,
+                overbar,
+                start1,
+                end1,
+                start2,
+                end2,
+            ),
+            Direction::EAST | Direction::WEST => self.svg_horizontal(
+                &mut canvas,
+                dir,
+                show_pin,
+                show_alt,
+                overbar,
+                start1,
+                end1,
+                start2,
+                end2,
+            ),
+        }
+
+        canvas.finish()
+    }
+
+    fn svg_vertical(
+        &self,
+        canvas: &mut Canvas,
+        dir: Direction,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+        lstart: usize,
+        lend: usize,
+        rstart: usize,
+        rend: usize,
+    ) {
+        let (lmax, lmaxes) = self.max_name_width(lstart, lend, show_alt, overbar);
+        let (rmax, rmaxes) = self.max_name_width(rstart, rend, show_alt, overbar);
+        let (lpin_width, rpin_width) = match show_pin {
+            PinGap::NONE => (1, 1),
+            PinGap::PIN1 => (
+                self.max_pin_width(lstart, lend) + 1,
+                self.max_pin_width(rstart, rend) + 1,
+            ),
+            PinGap::PIN2 => (
+                self.max_pin_width(lstart, lend) + 2,
+                self.max_pin_width(rstart, rend) + 2,
+            ),
+        };
+        let rows = self.dip / 2;
+        let body_x = MARGIN + (lmax + lpin_width) * CHAR_WIDTH + STUB_LENGTH;
+        let body_y = MARGIN;
+        let body_w = (self.dip_width() + 1) * CHAR_WIDTH;
+        let body_h = rows * LINE_HEIGHT;
+        let notch = if dir == Direction::NORTH {
+            Edge::TOP
+        } else {
+            Edge::BOTTOM
+        };
+        canvas.body(body_x, body_y, body_w, body_h, Some(notch));
+
+        let lname_x = body_x - STUB_LENGTH - lpin_width * CHAR_WIDTH;
+        let rname_x = body_x + body_w + STUB_LENGTH + rpin_width * CHAR_WIDTH;
+        let mut lpin = lstart;
+        let mut rpin = rstart;
+        for row in 0..rows {
+            let y = body_y + row * LINE_HEIGHT + LINE_HEIGHT / 2;
+            let (ltype, rtype) = (self.pins[&lpin].pin_type(), self.pins[&rpin].pin_type());
+            let y_stub = y - STUB_WIDTH / 2;
+            canvas.stub(body_x - STUB_LENGTH, y_stub, STUB_LENGTH, STUB_WIDTH, ltype);
+            canvas.stub(body_x + body_w, y_stub, STUB_LENGTH, STUB_WIDTH, rtype);
+            if show_pin != PinGap::NONE {
+                let x = body_x - STUB_LENGTH - CHAR_WIDTH / 2;
+                canvas.text(x, y, Anchor::END, false, &lpin.to_string());
+                let x = body_x + body_w + STUB_LENGTH + CHAR_WIDTH / 2;
+                canvas.text(x, y, Anchor::START, false, &rpin.to_string());
+            }
+            let pin = &self.pins[&lpin];
+            let mut x = lname_x;
+            for (c, width) in lmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::END, false, pin, c, overbar);
+                }
+                x -= (width + 1) * CHAR_WIDTH;
+            }
+            let pin = &self.pins[&rpin];
+            let mut x = rname_x;
+            for (c, width) in rmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::START, false, pin, c, overbar);
+                }
+                x += (width + 1) * CHAR_WIDTH;
+            }
+            if lpin == 1 {
+                canvas.dot(body_x + CHAR_WIDTH, y);
+            }
+            if rpin == 1 {
+                canvas.dot(body_x + body_w - CHAR_WIDTH, y);
+            }
+            lpin = self.next_pin(lpin, lstart, lend);
+            rpin = self.next_pin(rpin, rstart, rend);
+        }
+        let center_x = body_x + body_w / 2;
+        canvas.text(
+            center_x,
+            body_y + body_h / 2,
+            Anchor::MIDDLE,
+            true,
+            &self.name,
+        );
+
+        let mut bottom = body_y + body_h;
+        if show_pin != PinGap::NONE {
+            bottom += LINE_HEIGHT;
+            canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.title);
+        }
+        let right = rname_x + rmax * CHAR_WIDTH;
+        canvas.size(right + MARGIN, bottom + MARGIN);
+    }
+
+    fn svg_horizontal(
+        &self,
+        canvas: &mut Canvas,
+        dir: Direction,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+        tstart: usize,
+        tend: usize,
+        bstart: usize,
+        bend: usize,
+    ) {
+        let (tmax, tmaxes) = self.max_name_width(tstart, tend, show_alt, overbar);
+        let (bmax, bmaxes) = self.max_name_width(bstart, bend, show_alt, overbar);
+        let (tpin_height, bpin_height) = match show_pin {
+            PinGap::NONE => (1, 1),
+            PinGap::PIN1 => (
+                self.max_pin_width(tstart, tend) + 1,
+                self.max_pin_width(bstart, bend) + 1,
+            ),
+            PinGap::PIN2 => (
+                self.max_pin_width(tstart, tend) + 2,
+                self.max_pin_width(bstart, bend) + 2,
+            ),
+        };
+        let columns = self.dip / 2;
+        let body_x = MARGIN;
+        let body_y = MARGIN + (tmax + tpin_height) * CHAR_WIDTH + STUB_LENGTH;
+        let body_w = columns * 2 * CHAR_WIDTH;
+        let body_h = (self.dip_height() - 1) * LINE_HEIGHT;
+        let notch = if dir == Direction::EAST {
+            Edge::RIGHT
+        } else {
+            Edge::LEFT
+        };
+        canvas.body(body_x, body_y, body_w, body_h, Some(notch));
+
+        let tname_y = body_y - STUB_LENGTH - tpin_height * CHAR_WIDTH;
+        let bname_y = body_y + body_h + STUB_LENGTH + bpin_height * CHAR_WIDTH;
+        let mut tpin = tstart;
+        let mut bpin = bstart;
+        for column in 0..columns {
+            let x = body_x + column * 2 * CHAR_WIDTH + CHAR_WIDTH;
+            let (ttype, btype) = (self.pins[&tpin].pin_type(), self.pins[&bpin].pin_type());
+            let x_stub = x - STUB_WIDTH / 2;
+            canvas.stub(x_stub, body_y - STUB_LENGTH, STUB_WIDTH, STUB_LENGTH, ttype);
+            canvas.stub(x_stub, body_y + body_h, STUB_WIDTH, STUB_LENGTH, btype);
+            if show_pin != PinGap::NONE {
+                let y = body_y - STUB_LENGTH - CHAR_WIDTH / 2;
+                canvas.vertical_text(x, y, Anchor::START, &tpin.to_string());
+                let y = body_y + body_h + STUB_LENGTH + CHAR_WIDTH / 2;
+                canvas.vertical_text(x, y, Anchor::END, &bpin.to_string());
+            }
+            let pin = &self.pins[&tpin];
+            let mut y = tname_y;
+            for (c, width) in tmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::START, true, pin, c, overbar);
+                }
+                y -= (width + 1) * CHAR_WIDTH;
+            }
+            let pin = &self.pins[&bpin];
+            let mut y = bname_y;
+            for (c, width) in bmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::END, true, pin, c, overbar);
+                }
+                y += (width + 1) * CHAR_WIDTH;
+            }
+            if tpin == 1 {
+                canvas.dot(x, body_y + CHAR_WIDTH);
+            }
+            if bpin == 1 {
+                canvas.dot(x, body_y + body_h - CHAR_WIDTH);
+            }
+            tpin = self.next_pin(tpin, tstart, tend);
+            bpin = self.next_pin(bpin, bstart, bend);
+        }
+        let center_x = body_x + body_w / 2;
+        canvas.text(
+            center_x,
+            body_y + body_h / 2,
+            Anchor::MIDDLE,
+            false,
+            &self.name,
+        );
+
+        let mut bottom = bname_y + bmax * CHAR_WIDTH;
+        if show_pin != PinGap::NONE {
+            bottom += LINE_HEIGHT;
+            canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.title);
+        }
+        canvas.size(body_x + body_w + MARGIN, bottom + MARGIN);
+    }
+
+    fn svg_sip_vertical(
+        &self,
+        canvas: &mut Canvas,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+        start: usize,
+        end: usize,
+        first: bool,
+    ) {
+        let (lpins, rpins) = self.sip_sides(start, end, first);
+        let (lmax, lmaxes) = self.max_names_width(&lpins, show_alt, overbar);
+        let (rmax, rmaxes) = self.max_names_width(&rpins, show_alt, overbar);
+        let pin_width = |pins: &[usize]| match show_pin {
+            _ if pins.is_empty() => 0,
+            PinGap::NONE => 1,
+            PinGap::PIN1 => self.max_pins_width(pins) + 1,
+            PinGap::PIN2 => self.max_pins_width(pins) + 2,
+        };
+        let (lpin_width, rpin_width) = (pin_width(&lpins), pin_width(&rpins));
+        let rows = self.dip;
+        let body_x = if lpins.is_empty() {
+            MARGIN
+        } else {
+            MARGIN + (lmax + lpin_width) * CHAR_WIDTH + STUB_LENGTH
+        };
+        let body_y = MARGIN;
+        let body_w = 4 * CHAR_WIDTH;
+        let body_h = rows * LINE_HEIGHT;
+        canvas.body(body_x, body_y, body_w, body_h, None);
+
+        let lname_x = body_x - STUB_LENGTH - lpin_width * CHAR_WIDTH;
+        let rname_x = body_x + body_w + STUB_LENGTH + rpin_width * CHAR_WIDTH;
+        let mut pin = start;
+        for row in 0..rows {
+            let y = body_y + row * LINE_HEIGHT + LINE_HEIGHT / 2;
+            let left = lpins.contains(&pin);
+            let (stub_x, pin_x, dot_x, name_x, maxes, anchor) = if left {
+                (
+                    body_x - STUB_LENGTH,
+                    body_x - STUB_LENGTH - CHAR_WIDTH / 2,
+                    body_x + CHAR_WIDTH,
+                    lname_x,
+                    &lmaxes,
+                    Anchor::END,
+                )
+            } else {
+                (
+                    body_x + body_w,
+                    body_x + body_w + STUB_LENGTH + CHAR_WIDTH / 2,
+                    body_x + body_w - CHAR_WIDTH,
+                    rname_x,
+                    &rmaxes,
+                    Anchor::START,
+                )
+            };
+            let pin_name = &self.pins[&pin];
+            canvas.stub(
+                stub_x,
+                y - STUB_WIDTH / 2,
+                STUB_LENGTH,
+                STUB_WIDTH,
+                pin_name.pin_type(),
+            );
+            if show_pin != PinGap::NONE {
+                canvas.text(pin_x, y, anchor, false, &pin.to_string());
+            }
+            let mut x = name_x;
+            for (c, width) in maxes.iter().enumerate() {
+                if c < pin_name.names().len() {
+                    canvas.name(x, y, anchor, false, pin_name, c, overbar);
+                }
+                let step = (width + 1) * CHAR_WIDTH;
+                x = if left { x - step } else { x + step };
+            }
+            if pin == 1 {
+                canvas.dot(dot_x, y);
+            }
+            pin = self.next_pin(pin, start, end);
+        }
+        let center_x = body_x + body_w / 2;
+        canvas.text(
+            center_x,
+            body_y + body_h / 2,
+            Anchor::MIDDLE,
+            true,
+            &self.name,
+        );
+
+        let mut bottom = body_y + body_h;
+        if show_pin != PinGap::NONE {
+            bottom += LINE_HEIGHT;
+            canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.title);
+        }
+        let right = if rpins.is_empty() {
+            body_x + body_w
+        } else {
+            rname_x + rmax * CHAR_WIDTH
+        };
+        canvas.size(right + MARGIN, bottom + MARGIN);
+    }
+
+    fn svg_sip_horizontal(
+        &self,
+        canvas: &mut Canvas,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+        start: usize,
+        end: usize,
+        first: bool,
+    ) {
+        let (tpins, bpins) = self.sip_sides(start, end, first);
+        let (tmax, tmaxes) = self.max_names_width(&tpins, show_alt, overbar);
+        let (bmax, bmaxes) = self.max_names_width(&bpins, show_alt, overbar);
+        let pin_height = |pins: &[usize]| match show_pin {
+            _ if pins.is_empty() => 0,
+            PinGap::NONE => 1,
+            PinGap::PIN1 => self.max_pins_width(pins) + 1,
+            PinGap::PIN2 => self.max_pins_width(pins) + 2,
+        };
+        let (tpin_height, bpin_height) = (pin_height(&tpins), pin_height(&bpins));
+        let columns = self.dip;
+        let body_x = MARGIN;
+        let body_y = if tpins.is_empty() {
+            MARGIN
+        } else {
+            MARGIN + (tmax + tpin_height) * CHAR_WIDTH + STUB_LENGTH
+        };
+        let body_w = columns * 2 * CHAR_WIDTH;
+        let body_h = 2 * LINE_HEIGHT;
+        canvas.body(body_x, body_y, body_w, body_h, None);
+
+        let tname_y = body_y - STUB_LENGTH - tpin_height * CHAR_WIDTH;
+        let bname_y = body_y + body_h + STUB_LENGTH + bpin_height * CHAR_WIDTH;
+        let mut pin = start;
+        for column in 0..columns {
+            let x = body_x + column * 2 * CHAR_WIDTH + CHAR_WIDTH;
+            let top = tpins.contains(&pin);
+            let pin_name = &self.pins[&pin];
+            let stub_y = if top {
+                body_y - STUB_LENGTH
+            } else {
+                body_y + body_h
+            };
+            canvas.stub(
+                x - STUB_WIDTH / 2,
+                stub_y,
+                STUB_WIDTH,
+                STUB_LENGTH,
+                pin_name.pin_type(),
+            );
+            if show_pin != PinGap::NONE {
+                if top {
+                    let y = body_y - STUB_LENGTH - CHAR_WIDTH / 2;
+                    canvas.vertical_text(x, y, Anchor::START, &pin.to_string());
+                } else {
+                    let y = body_y + body_h + STUB_LENGTH + CHAR_WIDTH / 2;
+                    canvas.vertical_text(x, y, Anchor::END, &pin.to_string());
+                }
+            }
+            let (mut y, maxes) = if top {
+                (tname_y, &tmaxes)
+            } else {
+                (bname_y, &bmaxes)
+            };
+            for (c, width) in maxes.iter().enumerate() {
+                if c < pin_name.names().len() {
+                    let anchor = if top { Anchor::START } else { Anchor::END };
+                    canvas.name(x, y, anchor, true, pin_name, c, overbar);
+                }
+                let step = (width + 1) * CHAR_WIDTH;
+                y = if top { y - step } else { y + step };
+            }
+            if pin == 1 {
+                let y = if top {
+                    body_y + CHAR_WIDTH
+                } else {
+                    body_y + body_h - CHAR_WIDTH
+                };
+                canvas.dot(x, y);
+            }
+            pin = self.next_pin(pin, start, end);
+        }
+        let center_x = body_x + body_w / 2;
+        canvas.text(
+            center_x,
+            body_y + body_h / 2,
+            Anchor::MIDDLE,
+            false,
+            &self.name,
+        );
+
+        let mut bottom = if bpins.is_empty() {
+            body_y + body_h
+        } else {
+            bname_y + bmax * CHAR_WIDTH
+        };
+        if show_pin != PinGap::NONE {
+            bottom += LINE_HEIGHT;
+            canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.title);
+        }
+        canvas.size(body_x + body_w + MARGIN, bottom + MARGIN);
+    }
+
+    fn svg_quad(
+        &self,
+        canvas: &mut Canvas,
+        dir: Direction,
+        side: Side,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+    ) {
+        let edges = self.quad.unwrap().edges(dir, side);
+        let (lmax, lmaxes) = self.max_names_width(&edges.left, show_alt, overbar);
+        let (rmax, rmaxes) = self.max_names_width(&edges.right, show_alt, overbar);
+        let (tmax, tmaxes) = self.max_names_width(&edges.top, show_alt, overbar);
+        let (bmax, bmaxes) = self.max_names_width(&edges.bottom, show_alt, overbar);
+        let pin_width = |pins: &[usize]| match show_pin {
+            PinGap::NONE => 1,
+            PinGap::PIN1 => self.max_pins_width(pins) + 1,
+            PinGap::PIN2 => self.max_pins_width(pins) + 2,
+        };
+        let pitch = LINE_HEIGHT;
+        let body_x = MARGIN + (lmax + pin_width(&edges.left)) * CHAR_WIDTH + STUB_LENGTH;
+        let body_y = MARGIN + (tmax + pin_width(&edges.top)) * CHAR_WIDTH + STUB_LENGTH;
+        let body_w = (edges.top.len() + 1) * pitch;
+        let body_h = (edges.left.len() + 1) * pitch;
+        canvas.body(body_x, body_y, body_w, body_h, None);
+
+        let lname_x = body_x - STUB_LENGTH - pin_width(&edges.left) * CHAR_WIDTH;
+        let rname_x = body_x + body_w + STUB_LENGTH + pin_width(&edges.right) * CHAR_WIDTH;
+        for (row, (&lpin, &rpin)) in edges.left.iter().zip(&edges.right).enumerate() {
+            let y = body_y + (row + 1) * pitch;
+            let (ltype, rtype) = (self.pins[&lpin].pin_type(), self.pins[&rpin].pin_type());
+            let y_stub = y - STUB_WIDTH / 2;
+            canvas.stub(body_x - STUB_LENGTH, y_stub, STUB_LENGTH, STUB_WIDTH, ltype);
+            canvas.stub(body_x + body_w, y_stub, STUB_LENGTH, STUB_WIDTH, rtype);
+            if show_pin != PinGap::NONE {
+                let x = body_x - STUB_LENGTH - CHAR_WIDTH / 2;
+                canvas.text(x, y, Anchor::END, false, &lpin.to_string());
+                let x = body_x + body_w + STUB_LENGTH + CHAR_WIDTH / 2;
+                canvas.text(x, y, Anchor::START, false, &rpin.to_string());
+            }
+            let pin = &self.pins[&lpin];
+            let mut x = lname_x;
+            for (c, width) in lmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::END, false, pin, c, overbar);
+                }
+                x -= (width + 1) * CHAR_WIDTH;
+            }
+            let pin = &self.pins[&rpin];
+            let mut x = rname_x;
+            for (c, width) in rmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::START, false, pin, c, overbar);
+                }
+                x += (width + 1) * CHAR_WIDTH;
+            }
+            if lpin == 1 {
+                canvas.dot(body_x + CHAR_WIDTH, y);
+            }
+            if rpin == 1 {
+                canvas.dot(body_x + body_w - CHAR_WIDTH, y);
+            }
+        }
+
+        let tname_y = body_y - STUB_LENGTH - pin_width(&edges.top) * CHAR_WIDTH;
+        let bname_y = body_y + body_h + STUB_LENGTH + pin_width(&edges.bottom) * CHAR_WIDTH;
+        for (column, (&tpin, &bpin)) in edges.top.iter().zip(&edges.bottom).enumerate() {
+            let x = body_x + (column + 1) * pitch;
+            let (ttype, btype) = (self.pins[&tpin].pin_type(), self.pins[&bpin].pin_type());
+            let x_stub = x - STUB_WIDTH / 2;
+            canvas.stub(x_stub, body_y - STUB_LENGTH, STUB_WIDTH, STUB_LENGTH, ttype);
+            canvas.stub(x_stub, body_y + body_h, STUB_WIDTH, STUB_LENGTH, btype);
+            if show_pin != PinGap::NONE {
+                let y = body_y - STUB_LENGTH - CHAR_WIDTH / 2;
+                canvas.vertical_text(x, y, Anchor::START, &tpin.to_string());
+                let y = body_y + body_h + STUB_LENGTH + CHAR_WIDTH / 2;
+                canvas.vertical_text(x, y, Anchor::END, &bpin.to_string());
+            }
+            let pin = &self.pins[&tpin];
+            let mut y = tname_y;
+            for (c, width) in tmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::START, true, pin, c, overbar);
+                }
+                y -= (width + 1) * CHAR_WIDTH;
+            }
+            let pin = &self.pins[&bpin];
+            let mut y = bname_y;
+            for (c, width) in bmaxes.iter().enumerate() {
+                if c < pin.names().len() {
+                    canvas.name(x, y, Anchor::END, true, pin, c, overbar);
+                }
+                y += (width + 1) * CHAR_WIDTH;
+            }
+            if tpin == 1 {
+                canvas.dot(x, body_y + CHAR_WIDTH);
+            }
+            if bpin == 1 {
+                canvas.dot(x, body_y + body_h - CHAR_WIDTH);
+            }
+        }
+        let center_x = body_x + body_w / 2;
+        canvas.text(
+            center_x,
+            body_y + body_h / 2,
+            Anchor::MIDDLE,
+            false,
+            &self.name,
+        );
+
+        let mut bottom = bname_y + bmax * CHAR_WIDTH;
+        if show_pin != PinGap::NONE {
+            bottom += LINE_HEIGHT;
+            canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.title);
+        }
+        canvas.size(rname_x + rmax * CHAR_WIDTH + MARGIN, bottom + MARGIN);
+    }
+
+    fn svg_pga(
+        &self,
+        canvas: &mut Canvas,
+        dir: Direction,
+        side: Side,
+        show_pin: PinGap,
+        show_alt: AltNames,
+        overbar: bool,
+    ) {
+        let cells = self.grid.unwrap().cells(dir, side);
+        let lines = self.name_columns(show_alt);
+        let names_width = self
+            .pins()
+            .flat_map(|(_, pin)| pin.names().into_iter().take(lines))
+            .map(print::width)
+            .max()
+            .unwrap_or(0);
+        let label_width = cells.rows.iter().map(|l| l.len()).max().unwrap_or(0);
+        let cell_w = (max(names_width, 2) + 1) * CHAR_WIDTH;
+        let cell_h = (lines + 1) * LINE_HEIGHT;
+        let body_x = MARGIN + (label_width + 1) * CHAR_WIDTH;
+        let body_y = MARGIN + LINE_HEIGHT;
+        let body_w = cells.columns.len() * cell_w;
+        let body_h = cells.rows.len() * cell_h;
+        canvas.body(body_x, body_y, body_w, body_h, None);
+
+        for (c, label) in cells.columns.iter().enumerate() {
+            let x = body_x + c * cell_w + cell_w / 2;
+            canvas.text(x, MARGIN + LINE_HEIGHT / 2, Anchor::MIDDLE, false, label);
+        }
+        for (r, (row, label)) in cells.pins.iter().zip(&cells.rows).enumerate() {
+            let y = body_y + r * cell_h + LINE_HEIGHT / 2;
+            canvas.text(body_x - CHAR_WIDTH / 2, y, Anchor::END, false, label);
+            for (c, &n) in row.iter().enumerate() {
+                let x = body_x + c * cell_w + cell_w / 2;
+                if n == 1 {
+                    canvas.dot(
+                        body_x + CHAR_WIDTH / 2,
+                        body_y + r * cell_h + CHAR_WIDTH / 2,
+                    );
+                }
+                let pin = match self.pin(n) {
+                    None => continue,
+                    Some(pin) => pin,
+                };
+                canvas.stub(
+                    x - STUB_WIDTH / 2,
+                    y - STUB_WIDTH / 2,
+                    STUB_WIDTH,
+                    STUB_WIDTH,
+                    pin.pin_type(),
+                );
+                for l in 0..min(lines, pin.names().len()) {
+                    let y = y + (l + 1) * LINE_HEIGHT;
+                    canvas.name(x, y, Anchor::MIDDLE, false, pin, l, overbar);
+                }
+            }
+        }
+        let center_x = body_x + body_w / 2;
+        let mut bottom = body_y + body_h + LINE_HEIGHT;
+        canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.name);
+        if show_pin != PinGap::NONE {
+            bottom += LINE_HEIGHT;
+            canvas.text(center_x, bottom, Anchor::MIDDLE, false, &self.title);
+        }
+        canvas.size(body_x + body_w + MARGIN, bottom + MARGIN);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Edge {
+    TOP,
+    RIGHT,
+    BOTTOM,
+    LEFT,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Anchor {
+    START,
+    MIDDLE,
+    END,
+}
+
+impl Anchor {
+    fn name(self) -> &'static str {
+        match self {
+            Anchor::START => "start",
+            Anchor::MIDDLE => "middle",
+            Anchor::END => "end",
+        }
+    }
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    elements: Vec<String>,
+}
+
+impl Canvas {
+    fn new() -> Canvas {
+        Canvas {
+            width: 0,
+            height: 0,
+            elements: Vec::new(),
+        }
+    }
+
+    fn size(&mut self, width: usize, height: usize) {
+        self.width = max(self.width, width);
+        self.height = max(self.height, height);
+    }
+
+    /// Package outline with a semicircle notch on the `notch` edge, if any.
+    fn body(&mut self, x: usize, y: usize, w: usize, h: usize, notch: Option<Edge>) {
+        let r = CHAR_WIDTH;
+        let (cx, cy) = (x + w / 2, y + h / 2);
+        let path = match notch {
+            None => format!("M{},{} H{} V{} H{} Z", x, y, x + w, y + h, x),
+            Some(Edge::TOP) => format!(
+                "M{},{} H{} A{},{} 0 0,0 {},{} H{} V{} H{} Z",
+                x,
+                y,
+                cx - r,
+                r,
+                r,
+                cx + r,
+                y,
+                x + w,
+                y + h,
+                x
+            ),
+            Some(Edge::BOTTOM) => format!(
+                "M{},{} H{} V{} H{} A{},{} 0 0,0 {},{} H{} Z",
+                x,
+                y,
+                x + w,
+                y + h,
+                cx + r,
+                r,
+                r,
+                cx - r,
+                y + h,
+                x
+            ),
+            Some(Edge::RIGHT) => format!(
+                "M{},{} H{} V{} A{},{} 0 0,0 {},{} V{} H{} Z",
+                x,
+                y,
+                x + w,
+                cy - r,
+                r,
+                r,
+                x + w,
+                cy + r,
+                y + h,
+                x
+            ),
+            Some(Edge::LEFT) => format!(
+                "M{},{} H{} V{} H{} V{} A{},{} 0 0,0 {},{} Z",
+                x,
+                y,
+                x + w,
+                y + h,
+                x,
+                cy + r,
+                r,
+                r,
+                x,
+                cy - r
+            ),
+        };
+        self.elements
+            .push(format!(r#"<path class="body" d="{}"/>"#, path));
+    }
+
+    /// Pin stub, classified by electrical `pin_type` if any.
+    fn stub(&mut self, x: usize, y: usize, w: usize, h: usize, pin_type: Option<PinType>) {
+        let class = match pin_type {
+            None => String::from("pin"),
+            Some(t) => format!("pin {}", t),
+        };
+        self.elements.push(format!(
+            r#"<rect class="{}" x="{}" y="{}" width="{}" height="{}"/>"#,
+            class, x, y, w, h
+        ));
+    }
+
+    fn dot(&mut self, x: usize, y: usize) {
+        self.elements.push(format!(
+            r#"<circle class="pin1" cx="{}" cy="{}" r="{}"/>"#,
+            x,
+            y,
+            CHAR_WIDTH / 3
+        ));
+    }
+
+    /// Text centered vertically at `y`, optionally rotated to read top to bottom.
+    fn text(&mut self, x: usize, y: usize, anchor: Anchor, rotate: bool, text: &str) {
+        let transform = if rotate {
+            format!(r#" transform="rotate(90 {} {})""#, x, y)
+        } else {
+            String::new()
+        };
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="{}"{}>{}</text>"#,
+            x,
+            y,
+            anchor.name(),
+            transform,
+            escape(text)
+        ));
+    }
+
+    /// `column`-th name of `pin`, rotated to read bottom to top if
+    /// `vertical` is true. Active-low signal is overlined if `overbar`
+    /// is true.
+    fn name(
+        &mut self,
+        x: usize,
+        y: usize,
+        anchor: Anchor,
+        vertical: bool,
+        pin: &PinName,
+        column: usize,
+        overbar: bool,
+    ) {
+        let (text, class) = match pin.signal(column) {
+            Some(signal) if overbar && pin.is_active_low(column) => (signal, r#" class="overbar""#),
+            _ => (pin.names()[column], ""),
+        };
+        let transform = if vertical {
+            format!(r#" transform="rotate(-90 {} {})""#, x, y)
+        } else {
+            String::new()
+        };
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="{}"{}{}>{}</text>"#,
+            x,
+            y,
+            anchor.name(),
+            class,
+            transform,
+            escape(text)
+        ));
+    }
+
+    /// Text centered horizontally at `x`, rotated to read bottom to top.
+    fn vertical_text(&mut self, x: usize, y: usize, anchor: Anchor, text: &str) {
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="{}" transform="rotate(-90 {} {})">{}</text>"#,
+            x,
+            y,
+            anchor.name(),
+            x,
+            y,
+            escape(text)
+        ));
+    }
+
+    fn finish(self) -> Vec<String> {
+        let mut out = Vec::new();
+        out.push(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            self.width, self.height, self.width, self.height
+        ));
+        out.push(String::from("<style>"));
+        out.push(format!(
+            "  text {{ font-family: monospace; font-size: {}px; dominant-baseline: central; }}",
+            FONT_SIZE
+        ));
+        out.push(String::from(
+            "  .body { fill: #f8f8f8; stroke: black; stroke-width: 2; }",
+        ));
+        out.push(String::from("  .pin { fill: #c0c0c0; stroke: black; }"));
+        out.push(String::from("  .pin.power { fill: #e04040; }"));
+        out.push(String::from("  .pin.ground { fill: #404040; }"));
+        out.push(String::from("  .pin.no-connect { fill: white; }"));
+        out.push(String::from("  .pin1 { fill: black; }"));
+        out.push(String::from("  .overbar { text-decoration: overline; }"));
+        out.push(String::from("</style>"));
+        for element in self.elements {
+            out.push(format!("  {}", element));
+        }
+        out.push(String::from("</svg>"));
+
+        out
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[test]
+fn test_svg_output() {
+    use std::str::FromStr;
+    let dip = Dip::from_str(
+        r#"
+        name = "ATtiny412"
+        dip = 4
+        width = 300
+        1 = "VDD"
+        2 = "PA6, A6"
+        3 = "PA7, A7"
+        4 = "PA1 & <B>""#,
+    )
+    .unwrap();
+    let svg = dip.svg(
+        Direction::NORTH,
+        Side::TOP,
+        PinGap::PIN1,
+        AltNames::ALL,
+        false,
+    );
+    assert!(svg[0].starts_with("<svg "));
+    assert_eq!(svg.last().unwrap(), "</svg>");
+    assert_eq!(
+        svg.iter().filter(|l| l.contains(r#"class="pin""#)).count(),
+        4
+    );
+    assert_eq!(
+        svg.iter().filter(|l| l.contains(r#"class="pin1""#)).count(),
+        1
+    );
+    assert!(svg.iter().any(|l| l.contains(">A7</text>")));
+    assert!(svg
+        .iter()
+        .any(|l| l.contains(">PA1 &amp; &lt;B&gt;</text>")));
+
+    // a cell fits 3 overlined characters, not 6 code points
+    let pga = Dip::from_str(
+        "name = \"X\"\npackage = \"pga\"\ngrid = [1, 1]\nA1 = \"R\u{305}S\u{305}T\u{305}\"",
+    )
+    .unwrap();
+    let svg = pga.svg(
+        Direction::NORTH,
+        Side::TOP,
+        PinGap::NONE,
+        AltNames::NONE,
+        false,
+    );
+    assert!(svg.contains(&String::from(
+        r#"  <path class="body" d="M30,30 H70 V70 H30 Z"/>"#
+    )));
+}
@@ -0,0 +1,489 @@
+use crate::dip::{Dip, Package};
+use crate::error::DipError;
+use crate::pga;
+use crate::pin::PinName;
+use csv::{ReaderBuilder, StringRecord, Trim, WriterBuilder};
+use std::cmp::max;
+use toml::map::Map;
+use toml::Value;
+
+/// Headings of columns of package properties in a pin table and their
+/// keys in specification.
+const PROPERTIES: [(&str, &str); 9] = [
+    ("part", "name"),
+    ("title", "title"),
+    ("package", "package"),
+    ("width", "width"),
+    ("sides", "sides"),
+    ("pin1", "pin1"),
+    ("grid", "grid"),
+    ("numbering", "numbering"),
+    ("columns", "columns"),
+];
+
+impl Dip {
+    /// Parses pin table `s` of `delimiter` separated values, such as CSV
+    /// and TSV, as package `name` unless the table has a `part` column.
+    /// The header row names the columns; `pin` is a pin number, optional
+    /// `type` is an electrical type, `name` and `alt1`, `alt2`, ... or
+    /// headings in `columns` are names, and the others in `PROPERTIES`
+    /// are package properties, which are overridden by `properties`.
+    /// The pin count is the largest pin number.
+    pub fn parse_table(
+        s: &str,
+        delimiter: u8,
+        name: &str,
+        properties: &Map<String, Value>,
+    ) -> Result<Dip, Vec<DipError>> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .trim(Trim::All)
+            .from_reader(s.as_bytes());
+        let headers = match reader.headers() {
+            Err(err) => return Err(vec![from_csv(err)]),
+            Ok(headers) => headers.clone(),
+        };
+        let mut errors = Vec::new();
+        let mut records = Vec::new();
+        for record in reader.records() {
+            match record {
+                Err(err) => errors.push(from_csv(err)),
+                Ok(record) => records.push(record),
+            }
+        }
+
+        let mut table = Map::new();
+        table.insert("name".to_string(), Value::from(name));
+        for (c, heading) in headers.iter().enumerate() {
+            let key = match PROPERTIES
+                .iter()
+                .find(|(h, _)| heading.eq_ignore_ascii_case(h))
+            {
+                None => continue,
+                Some((_, key)) => key,
+            };
+            let mut value = None;
+            for record in &records {
+                let cell = record.get(c).unwrap_or("");
+                match &value {
+                    _ if cell.is_empty() => {}
+                    None => value = Some(cell),
+                    Some(v) if *v == cell => {}
+                    Some(_) => errors.push(syntax(
+                        format!("inconsistent {} {}", heading, cell),
+                        line(record),
+                    )),
+                }
+            }
+            if let Some(value) = value {
+                table.insert(key.to_string(), property_value(key, value));
+            }
+        }
+        for (key, value) in properties {
+            table.insert(key.to_string(), value.clone());
+        }
+        let dip = matches!(
+            table.get("package").and_then(Value::as_str),
+            None | Some("dip")
+        );
+        if dip && !table.contains_key("width") {
+            table.insert("width".to_string(), Value::Integer(300));
+        }
+
+        let columns: Vec<&str> = match table.get("columns").and_then(Value::as_array) {
+            None => Vec::new(),
+            Some(columns) => columns.iter().filter_map(Value::as_str).collect(),
+        };
+        let mut pin_column = None;
+        let mut type_column = None;
+        let mut name_columns = Vec::new();
+        for (c, heading) in headers.iter().enumerate() {
+            let index = match alt_number(heading) {
+                _ if heading.eq_ignore_ascii_case("pin") => {
+                    pin_column = Some(c);
+                    continue;
+                }
+                _ if heading.eq_ignore_ascii_case("type") => {
+                    type_column = Some(c);
+                    continue;
+                }
+                _ if PROPERTIES
+                    .iter()
+                    .any(|(h, _)| heading.eq_ignore_ascii_case(h)) =>
+                {
+                    continue
+                }
+                _ if heading.eq_ignore_ascii_case("name") => Some(0),
+                Some(n) => Some(n),
+                None => columns.iter().position(|column| *column == heading),
+            };
+            match index {
+                None => errors.push(syntax(format!("unknown column {}", heading), 1)),
+                Some(index) if name_columns.iter().any(|&(_, i)| i == index) => {
+                    errors.push(syntax(format!("duplicate column {}", heading), 1))
+                }
+                Some(index) => name_columns.push((c, index)),
+            }
+        }
+        let pin_column = match pin_column {
+            None => return Err(vec![syntax("no pin column".to_string(), 1)]),
+            Some(c) => c,
+        };
+
+        // pins of PGA are labeled by grid positions, which are checked
+        // by the specification
+        let pga = table.get("package").and_then(Value::as_str) == Some("pga");
+        let mut pins = Map::new();
+        let mut dip = 0;
+        for record in &records {
+            let number = record.get(pin_column).unwrap_or("");
+            let (key, n) = match number.parse::<usize>() {
+                Err(_) if pga && pga::is_label(number) => (number.to_string(), 0),
+                Err(_) => {
+                    errors.push(syntax(
+                        format!("invalid pin number {}", number),
+                        line(record),
+                    ));
+                    continue;
+                }
+                Ok(n) => (n.to_string(), n),
+            };
+            if pins.contains_key(&key) {
+                errors.push(DipError::DuplicatePin {
+                    key: format!("pin.{}", key),
+                    pin: n,
+                });
+                continue;
+            }
+            pins.insert(key, pin_value(record, &name_columns, type_column));
+            dip = max(dip, n);
+        }
+
+        if !pga {
+            table.insert("pins".to_string(), Value::Integer(dip as i64));
+        }
+        table.insert("pin".to_string(), Value::Table(pins));
+        match Dip::from_table(&table) {
+            Ok(dip) if errors.is_empty() => Ok(dip),
+            Ok(_) => Err(errors),
+            Err(mut more) => {
+                errors.append(&mut more);
+                Err(errors)
+            }
+        }
+    }
+
+    /// Returns lines of the pin table in `delimiter` separated values,
+    /// which can be read back by `parse_table`. Every row has package
+    /// properties, and variants are not included.
+    pub fn table(&self, delimiter: u8) -> Vec<String> {
+        let columns = self.pins().map(|(_, pin)| pin.names().len()).max();
+        let typed = self.pins().any(|(_, pin)| pin.pin_type().is_some());
+        let mut headers = vec!["pin".to_string()];
+        for c in 0..columns.unwrap_or(1) {
+            headers.push(match (self.columns.get(c), c) {
+                (Some(heading), _) => heading.to_string(),
+                (None, 0) => "name".to_string(),
+                (None, c) => format!("alt{}", c),
+            });
+        }
+        if typed {
+            headers.push("type".to_string());
+        }
+        let mut properties = vec![
+            ("part", self.name.to_string()),
+            ("title", self.title.to_string()),
+            ("package", self.package.name().to_string()),
+        ];
+        if self.package == Package::DIP || self.numbering.is_some() {
+            properties.push(("width", self.width.mil().to_string()));
+        }
+        if let Some(quad) = self.quad {
+            properties.push(("sides", format!("{}, {}", quad.vertical, quad.horizontal)));
+            properties.push(("pin1", quad.pin1.name().to_string()));
+        }
+        if let Some(grid) = self.grid {
+            properties.push(("grid", format!("{}, {}", grid.rows, grid.columns)));
+        }
+        if let Some(numbering) = self.numbering {
+            properties.push(("numbering", numbering.name().to_string()));
+        }
+        if !self.columns.is_empty() {
+            properties.push(("columns", self.columns.join(", ")));
+        }
+        let names = headers.len() - 1 - typed as usize;
+        headers.extend(properties.iter().map(|(heading, _)| heading.to_string()));
+
+        let mut writer = WriterBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_writer(Vec::new());
+        writer.write_record(&headers).unwrap();
+        for (n, pin) in self.pins() {
+            let mut record = vec![self.pin_label(n)];
+            record.extend(pin.names().iter().map(|name| name.to_string()));
+            record.resize(1 + names, String::new());
+            if typed {
+                record.push(pin.pin_type().map_or(String::new(), |t| t.to_string()));
+            }
+            record.extend(properties.iter().map(|(_, value)| value.to_string()));
+            writer.write_record(&record).unwrap();
+        }
+        let bytes = writer.into_inner().unwrap();
+
+        String::from_utf8(bytes)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Returns lines of TOML specification of the package.
+    pub fn toml(&self) -> Vec<String> {
+        let mut out = vec![
+            format!("name = {}", Value::String(self.name.to_string())),
+            format!("title = {}", Value::String(self.title.to_string())),
+        ];
+        if self.package == Package::DIP {
+            out.push(format!("dip = {}", self.dip));
+            out.push(format!("width = {}", self.width.mil()));
+        } else {
+            out.push(format!("package = \"{}\"", self.package.name()));
+            out.push(format!("pins = {}", self.dip));
+        }
+        if let Some(quad) = self.quad {
+            out.push(format!("sides = [{}, {}]", quad.vertical, quad.horizontal));
+            out.push(format!("pin1 = \"{}\"", quad.pin1.name()));
+        }
+        if let Some(grid) = self.grid {
+            out.push(format!("grid = [{}, {}]", grid.rows, grid.columns));
+        }
+        if let Some(numbering) = self.numbering {
+            out.push(format!("width = {}", self.width.mil()));
+            out.push(format!("numbering = \"{}\"", numbering.name()));
+        }
+        if !self.columns.is_empty() {
+            let columns: Vec<Value> = self
+                .columns
+                .iter()
+                .map(|c| Value::from(c.as_str()))
+                .collect();
+            out.push(format!("columns = {}", Value::Array(columns)));
+        }
+        for (n, pin) in self.pins() {
+            out.push(pin_toml(&self.pin_label(n), pin, &self.columns));
+        }
+        for (name, variant) in self.variants() {
+            out.push(String::new());
+            out.push(format!("[variant.{}]", toml_key(name)));
+            if variant.name != self.name {
+                out.push(format!("name = {}", Value::from(variant.name.as_str())));
+            }
+            if variant.title != self.title {
+                out.push(format!("title = {}", Value::from(variant.title.as_str())));
+            }
+            for (n, pin) in self.overridden_pins(variant) {
+                out.push(pin_toml(&variant.pin_label(n), pin, &self.columns));
+            }
+        }
+
+        out
+    }
+}
+
+/// Returns a TOML line of `pin` labeled `label`. Names after an empty
+/// name are written by headings of `columns` if all of them have one.
+fn pin_toml(label: &str, pin: &PinName, columns: &[String]) -> String {
+    let names = pin.names();
+    let positional = match names.iter().position(|name| name.is_empty()) {
+        Some(empty) if names.len() <= columns.len() => empty,
+        _ => names.len(),
+    };
+    let list = &names[..positional];
+    let value = if list.iter().any(|name| name.contains(',')) {
+        Value::Array(list.iter().map(|name| Value::from(*name)).collect())
+    } else {
+        Value::String(list.join(", "))
+    };
+    if positional == names.len() && pin.pin_type().is_none() {
+        return format!("{} = {}", label, value);
+    }
+    let mut fields = vec![format!("names = {}", value)];
+    for (name, heading) in names.iter().zip(columns).skip(positional) {
+        if !name.is_empty() {
+            fields.push(format!("{} = {}", toml_key(heading), Value::from(*name)));
+        }
+    }
+    if let Some(t) = pin.pin_type() {
+        fields.push(format!("type = \"{}\"", t));
+    }
+    format!("{} = {{ {} }}", label, fields.join(", "))
+}
+
+/// Returns `key` as a TOML key, which is quoted unless it is bare.
+fn toml_key(key: &str) -> String {
+    let bare = key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        Value::from(key).to_string()
+    }
+}
+
+/// Returns a pin table of names in `names` columns, which are pairs of
+/// a column and an index of the name, and optional type in `record`.
+fn pin_value(record: &StringRecord, names: &[(usize, usize)], type_column: Option<usize>) -> Value {
+    let size = names.iter().map(|&(_, i)| i + 1).max().unwrap_or(0);
+    let mut list = vec![""; size];
+    for &(c, i) in names {
+        list[i] = record.get(c).unwrap_or("");
+    }
+    while list.len() > 1 && list.last() == Some(&"") {
+        list.pop();
+    }
+    let mut pin = Map::new();
+    let list = list.into_iter().map(String::from).map(Value::String);
+    pin.insert("names".to_string(), Value::Array(list.collect()));
+    match type_column.and_then(|c| record.get(c)) {
+        None | Some("") => {}
+        Some(t) => {
+            pin.insert("type".to_string(), Value::String(t.to_string()));
+        }
+    }
+
+    Value::Table(pin)
+}
+
+/// Returns a value of package property `key` in a pin table cell
+/// `value`, where an array is comma separated.
+fn property_value(key: &str, value: &str) -> Value {
+    let integer = |v: &str| {
+        v.parse::<i64>()
+            .map_or_else(|_| Value::from(v), Value::from)
+    };
+    let list = value.split(',').map(str::trim);
+    match key {
+        "width" => integer(value),
+        "sides" | "grid" => Value::Array(list.map(integer).collect()),
+        "columns" => Value::Array(list.map(Value::from).collect()),
+        _ => Value::from(value),
+    }
+}
+
+/// Returns N of alternate name heading `heading`, like `alt1` or `Alt 1`
+/// in any case.
+fn alt_number(heading: &str) -> Option<usize> {
+    let (alt, n) = (heading.get(..3)?, &heading[3..]);
+    if !alt.eq_ignore_ascii_case("alt") {
+        return None;
+    }
+    n.trim_start().parse::<usize>().ok().filter(|&n| n > 0)
+}
+
+/// Returns the line number of `record`.
+fn line(record: &StringRecord) -> usize {
+    record.position().map_or(1, |p| p.line() as usize)
+}
+
+fn syntax(message: String, line: usize) -> DipError {
+    DipError::Syntax {
+        message,
+        line,
+        column: 1,
+    }
+}
+
+fn from_csv(err: csv::Error) -> DipError {
+    let line = err.position().map_or(1, |p| p.line() as usize);
+    syntax(err.to_string(), line)
+}
+
+#[test]
+fn test_table() {
+    use crate::dip::DipWidth;
+    use std::str::FromStr;
+    let none = Map::new();
+    let csv = "pin,name,alt1,type\n1,PA0,#SS,\n2,GND,,ground\n";
+    let dip = Dip::parse_table(csv, b',', "X", &none).unwrap();
+    assert_eq!(dip.pin(1).unwrap().names(), &["PA0", "#SS"]);
+    assert_eq!(dip.pin(2).unwrap().names(), &["GND"]);
+    assert_eq!(
+        dip.table(b','),
+        vec![
+            "pin,name,alt1,type,part,title,package,width",
+            "1,PA0,#SS,,X,X,dip,300",
+            "2,GND,,ground,X,X,dip,300"
+        ]
+    );
+    assert_eq!(
+        dip.toml()[4..],
+        [
+            "1 = \"PA0, #SS\"",
+            "2 = { names = \"GND\", type = \"ground\" }"
+        ]
+    );
+    let csv = "Pin,Name,Alt 1,ALT2\n1,PA0,#SS,MISO\n2,GND,,\n";
+    let dip = Dip::parse_table(csv, b',', "X", &none).unwrap();
+    assert_eq!(dip.pin(1).unwrap().names(), &["PA0", "#SS", "MISO"]);
+    assert_eq!(alt_number("alt0"), None);
+    assert_eq!(alt_number("Alternate"), None);
+    let csv = "pin,Func,Port,width,columns\n1,A0,PA0,600,\"Port, Func\"\n2,GND,,,\n";
+    let dip = Dip::parse_table(csv, b',', "X", &none).unwrap();
+    assert_eq!(dip.pin(1).unwrap().names(), &["PA0", "A0"]);
+    assert_eq!(dip.pin(2).unwrap().names(), &["", "GND"]);
+    assert_eq!(dip.width.mil(), 600);
+    assert_eq!(
+        Dip::parse_table(csv, b',', "X", &toml::toml! { width = 300 }).map(|dip| dip.width),
+        Ok(DipWidth::MIL300)
+    );
+    assert_eq!(
+        Dip::parse_table("pin,name,Port\n1,A\n2,B\n", b',', "X", &none).err(),
+        Some(vec![syntax("unknown column Port".to_string(), 1)])
+    );
+    let sip = "pin,name\n1,A\n2,B\n3,C\n";
+    assert_eq!(
+        Dip::parse_table(sip, b',', "X", &toml::toml! { package = "sip" }).map(|dip| dip.dip),
+        Ok(3)
+    );
+    let pga = Dip::from_str(
+        r#"
+        name = "P"
+        package = "pga"
+        grid = [2, 2]
+        A1 = "VCC"
+        B2 = "GND""#,
+    )
+    .unwrap();
+    // pin count of PGA is the number of defined pins, not of positions
+    assert_eq!((pga.dip, pga.pins().last().map(|(n, _)| n)), (2, Some(4)));
+    let csv = pga.table(b',').join("\n");
+    let back = Dip::parse_table(&csv, b',', "X", &none).unwrap();
+    assert_eq!(back.dip, pga.dip);
+    assert_eq!(back.toml(), pga.toml());
+    assert_eq!(
+        Dip::parse_table("pin\tname\n1\tA\nX\tB\n2\tC\n", b'\t', "X", &none).err(),
+        Some(vec![syntax("invalid pin number X".to_string(), 3)])
+    );
+}
+
+#[test]
+fn test_table_samples() {
+    use std::fs;
+    use std::str::FromStr;
+    for entry in fs::read_dir("samples").unwrap() {
+        let path = entry.unwrap().path();
+        let dip = Dip::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let csv = dip.table(b',').join("\n");
+        let back = Dip::parse_table(&csv, b',', "X", &Map::new()).unwrap();
+        // variants follow an empty line
+        let base: Vec<String> = dip
+            .toml()
+            .into_iter()
+            .take_while(|l| !l.is_empty())
+            .collect();
+        assert_eq!(back.toml(), base, "{:?}", path);
+    }
+}